@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use bevy_mod_sysfail::prelude::*;
+use eyre::WrapErr;
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("Could not find the config file")]
+struct MissingConfig;
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, bevy::log::LogPlugin::default()))
+        .add_systems(Update, (parse_number, load_config, verbose_config).chain());
+    app.update();
+}
+
+#[sysfail(Log<eyre::Report>)]
+fn parse_number() {
+    // Any `std::error::Error` can be converted into an `eyre::Report` with `?`
+    let number: u32 = "not a number".parse()?;
+    println!("This will never print: {number}");
+}
+
+#[sysfail(Log<eyre::Report, Error>)]
+fn load_config() {
+    Err(MissingConfig).wrap_err("Failed to load the game settings")?;
+}
+
+#[sysfail(LogVerbose<eyre::Report, Error>)]
+fn verbose_config() {
+    // `LogVerbose` prints the full chain of causes
+    Err(MissingConfig).wrap_err("Failed to load the game settings")?;
+}
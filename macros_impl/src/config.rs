@@ -0,0 +1,216 @@
+//! Parsing of the `#[sysfail(...)]` attribute arguments into a [`FnConfig`].
+//!
+//! This is independent from code generation, see [`crate::generate`].
+use syn::parse::{Parse, ParseStream};
+use syn::parse_quote;
+
+use crate::generate::is_named;
+
+/// The options of a `#[sysfail]` attribute.
+#[derive(Clone)]
+pub struct FnConfig {
+    pub error_type: syn::Type,
+    pub exclusive: bool,
+    pub on_error: Option<syn::Path>,
+    /// The flag options, such as `catch_panic`.
+    pub flags: Flags,
+    /// `error_type` in debug builds, with `debug_assertions`.
+    pub debug: Option<syn::Type>,
+    /// `error_type` in release builds, without `debug_assertions`.
+    pub release: Option<syn::Type>,
+    /// The tracing target of the callsite, the system's path by default.
+    pub target: Option<syn::LitStr>,
+    /// The name of the field of the logged message, `"message"` by default.
+    pub field: Option<syn::LitStr>,
+    /// The name of a `SystemSet` unit struct to generate next to the system.
+    pub set: Option<syn::Ident>,
+    /// The `FailureMode` value of a `Configured` failure.
+    pub mode_ref: Option<syn::Path>,
+    /// A function applied to the error of each `?` before converting it.
+    pub map: Option<syn::Path>,
+    /// How `error_type` depends on the generics of the system. Not an option,
+    /// set when generating the system.
+    pub failure_generics: FailureGenerics,
+}
+
+/// The options of a `#[sysfail]` attribute without value, except `nodedup`,
+/// which changes the `error_type` of [`FnConfig`].
+#[derive(Clone, Copy, Default)]
+pub struct Flags {
+    /// Catch panics in the system body, and handle them as errors.
+    pub catch_panic: bool,
+    /// The system returns the value of the body, or `Default::default()`
+    /// on errors.
+    pub forward: bool,
+    /// `?` on `Option`s returns a `NoneError` for `None`.
+    pub none_error: bool,
+}
+
+/// How the `Failure` type of a system depends on the system's generic
+/// parameters.
+///
+/// `static`s can't use the generic parameters of the enclosing function, so
+/// this decides how the `static` callsite of the system is defined.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailureGenerics {
+    /// It doesn't use them, or we can't know, as in `sysfail_block!`.
+    #[default]
+    Independent,
+    /// It is one of them, such as the `F` of `fn system<F: Failure>()`.
+    TypeParam,
+    /// It uses them, such as `Log<MyError<T>>` in `fn system<T>()`.
+    Dependent,
+}
+impl Default for FnConfig {
+    /// The configuration of `#[sysfail]` without arguments.
+    ///
+    /// Use struct update syntax to change the defaults, such as
+    /// `FnConfig { exclusive: true, ..FnConfig::default() }`.
+    fn default() -> Self {
+        Self {
+            // `Log` or `LogSimply`, depending on the `full` feature
+            error_type: parse_quote![::bevy_mod_sysfail::__macro::DefaultLog],
+            exclusive: false,
+            on_error: None,
+            flags: Flags::default(),
+            debug: None,
+            release: None,
+            target: None,
+            field: None,
+            set: None,
+            mode_ref: None,
+            map: None,
+            failure_generics: FailureGenerics::Independent,
+        }
+    }
+}
+impl FnConfig {
+    /// Parse the attribute arguments: `FailureType, flag, key = value, ...`.
+    ///
+    /// The failure type is optional.
+    pub fn parse_attrs(&mut self, input: ParseStream) -> syn::Result<()> {
+        let is_option = |input: ParseStream| {
+            let is_flag = || {
+                input
+                    .fork()
+                    .parse::<syn::Ident>()
+                    .is_ok_and(|i| FLAGS.contains(&&*i.to_string()))
+            };
+            input.peek(syn::Ident) && (input.peek2(syn::Token![=]) || is_flag())
+        };
+        let has_type = !input.is_empty() && !is_option(input);
+        if has_type {
+            self.error_type = input.parse()?;
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        let mut nodedup = None;
+        while !input.is_empty() {
+            let key: syn::Ident = input.parse()?;
+            match key.to_string().as_str() {
+                "nodedup" => nodedup = Some(key),
+                "default" if has_type => {
+                    let msg = "`default` can't be used together with a failure type";
+                    return Err(syn::Error::new_spanned(key, msg));
+                }
+                "default" => self.error_type = parse_quote!(crate::__sysfail_default),
+                "catch_panic" => self.flags.catch_panic = true,
+                "forward" => self.flags.forward = true,
+                "none_error" => self.flags.none_error = true,
+                "on_error" => self.on_error = Some(parse_value(input)?),
+                "debug" => self.debug = Some(parse_value(input)?),
+                "release" => self.release = Some(parse_value(input)?),
+                "target" => self.target = Some(parse_value(input)?),
+                "set" => self.set = Some(parse_value(input)?),
+                "mode_ref" => self.mode_ref = Some(parse_value(input)?),
+                "map" => self.map = Some(parse_value(input)?),
+                "field" => {
+                    let field: syn::LitStr = parse_value(input)?;
+                    if field.value() == "sysfail" {
+                        let msg = "the `sysfail` field name is reserved";
+                        return Err(syn::Error::new_spanned(field, msg));
+                    }
+                    self.field = Some(field);
+                }
+                _ => return Err(unknown_option(&key)),
+            }
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        match &self.mode_ref {
+            Some(_) if !has_type => {
+                self.error_type = parse_quote![
+                    ::bevy_mod_sysfail::prelude::Configured<
+                        ::std::boxed::Box<dyn ::std::error::Error>,
+                    >
+                ];
+            }
+            Some(mode_ref) if !is_named(&self.error_type, "Configured") => {
+                let msg = "`mode_ref` can only be used with the `Configured<...>` failure type";
+                return Err(syn::Error::new_spanned(mode_ref, msg));
+            }
+            None if is_named(&self.error_type, "Configured") => {
+                let msg = "`Configured` needs a `mode_ref = PATH` option";
+                return Err(syn::Error::new_spanned(&self.error_type, msg));
+            }
+            _ => {}
+        }
+        if let Some(nodedup) = nodedup {
+            self.error_type = without_dedup(&self.error_type, &nodedup)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replace `Log<T, Lvl, Clock>` by `LogSimply<T, Lvl>`, for the `nodedup` flag.
+fn without_dedup(ty: &syn::Type, nodedup: &syn::Ident) -> syn::Result<syn::Type> {
+    let not_log = || {
+        let msg = "`nodedup` can only be used with `Log<...>` failure types";
+        syn::Error::new_spanned(nodedup, msg)
+    };
+    let syn::Type::Path(mut ty) = ty.clone() else {
+        return Err(not_log());
+    };
+    let last = ty.path.segments.last_mut().ok_or_else(not_log)?;
+    if last.ident == "DefaultLog" {
+        return Ok(parse_quote![
+            ::bevy_mod_sysfail::prelude::LogSimply<::std::boxed::Box<dyn ::std::error::Error>>
+        ]);
+    }
+    if last.ident != "Log" {
+        return Err(not_log());
+    }
+    last.ident = syn::Ident::new("LogSimply", last.ident.span());
+    if let syn::PathArguments::AngleBracketed(args) = &mut last.arguments {
+        // `LogSimply` has no `Clock` parameter
+        args.args = args.args.iter().take(2).cloned().collect();
+    }
+    Ok(syn::Type::Path(ty))
+}
+
+/// The error for an unknown `key` option, listing the valid options.
+fn unknown_option(key: &syn::Ident) -> syn::Error {
+    let flags = FLAGS.iter().map(|flag| format!("`{flag}`"));
+    let values = VALUE_OPTIONS
+        .iter()
+        .map(|option| format!("`{option} = ...`"));
+    let options = flags.chain(values).collect::<Vec<_>>().join(", ");
+    let msg = format!("Unknown sysfail option `{key}`, valid options are: {options}");
+    syn::Error::new_spanned(key, msg)
+}
+
+/// Parse the `= value` part of a `key = value` option.
+fn parse_value<T: Parse>(input: ParseStream) -> syn::Result<T> {
+    input.parse::<syn::Token![=]>()?;
+    input.parse()
+}
+
+/// Options without values.
+const FLAGS: &[&str] = &["catch_panic", "default", "forward", "nodedup", "none_error"];
+
+/// Options of the form `key = value`.
+const VALUE_OPTIONS: &[&str] = &[
+    "on_error", "debug", "release", "target", "field", "set", "mode_ref", "map",
+];
@@ -1,41 +1,928 @@
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::parse_quote;
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_quote, parse_quote_spanned, spanned::Spanned, visit_mut::VisitMut};
 
-pub struct FnConfig {
-    pub error_type: syn::Type,
-    pub exclusive: bool,
+use crate::config::{FailureGenerics, FnConfig};
+
+const QUICK_MSG: &str = "#[sysfail] systems have no return types.";
+const ASYNC_MSG: &str = "#[sysfail] systems can't be `async`, bevy systems are synchronous. \
+    Spawn a task with `AsyncComputeTaskPool` in a regular system instead.";
+
+/// `Failure`s in `bevy_mod_sysfail` that use the callsite, they get one even
+/// when their error type depends on the system's generics.
+const LOG_TYPES: &[&str] = &[
+    "Log",
+    "LogSimply",
+    "LogOnce",
+    "LogVerbose",
+    "LogJson",
+    "LogThrottled",
+    "LogShared",
+    "LogN",
+    "LogWith",
+    "LogOnChange",
+    "LogSpanError",
+    "Configured",
+    "LogOrPanic",
+    "LogRedacted",
+    "LogPerSite",
+    "Eprintln",
+    "EmitWithSite",
+    "Aggregate",
+    "LogTimed",
+    "RoutedLog",
+    "DefaultLog",
+];
+
+/// The `Failure` type that uses the callsite, if any.
+///
+/// This is `ty` if its last path segment is one of the [`LOG_TYPES`], or one
+/// of the type parameters of a `Tee<A, B>`, `Sampled<F, N>`,
+/// `GracePeriod<F, N>` or `Defer<F>`.
+fn log_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(syn::TypePath { path, .. }) = ty else {
+        return None;
+    };
+    let last = path.segments.last()?;
+    if LOG_TYPES.iter().any(|log| last.ident == log) {
+        return Some(ty);
+    }
+    let syn::PathArguments::AngleBracketed(args) = &last.arguments else {
+        return None;
+    };
+    if !["Tee", "Sampled", "GracePeriod", "Defer"]
+        .iter()
+        .any(|wrapper| last.ident == wrapper)
+    {
+        return None;
+    }
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => log_type(ty),
+        _ => None,
+    })
 }
-impl FnConfig {
-    pub fn new() -> Self {
-        Self {
-            error_type: parse_quote![
-                ::bevy_mod_sysfail::prelude::Log<::std::boxed::Box<dyn ::std::error::Error>>
-            ],
-            exclusive: false,
+
+/// The log level of the `log_type` `Failure`, as an expression usable in a `static`.
+///
+/// For the logging `Failure`s of this crate, this uses their `Lvl` type
+/// parameter rather than `<log_type as Failure>::LEVEL`, because the error type
+/// parameter may reference the system's generics, which can't be used in
+/// `static`s.
+fn log_level(log_type: &syn::Type) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let from_failure = quote!(<#log_type as #prefix::Failure>::LEVEL);
+    let syn::Type::Path(syn::TypePath { path, .. }) = log_type else {
+        return from_failure;
+    };
+    let Some(last) = path.segments.last() else {
+        return from_failure;
+    };
+    let default_level = match last.ident.to_string().as_str() {
+        "Log" | "LogSimply" | "LogOnce" | "LogVerbose" | "LogJson" | "LogShared" | "LogOrPanic"
+        | "LogRedacted" | "LogPerSite" | "Eprintln" | "LogTimed" | "LogWith" | "LogOnChange"
+        | "LogSpanError" => {
+            quote!(#prefix::Level::WARN)
         }
+        // Don't log, but need a callsite for its location or target
+        "EmitWithSite" | "Aggregate" => return quote!(#prefix::Level::INFO),
+        _ => return from_failure,
+    };
+    let syn::PathArguments::AngleBracketed(args) = &last.arguments else {
+        return default_level;
+    };
+    match args.args.iter().nth(1) {
+        Some(syn::GenericArgument::Type(level)) => {
+            quote!(<#level as #prefix::LogLevelModifier>::LEVEL)
+        }
+        _ => default_level,
     }
 }
 
-const QUICK_MSG: &str = "#[sysfail] systems have no return types.";
+fn is_routed(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(syn::TypePath { path, .. })
+        if path.segments.last().is_some_and(|p| p.ident == "RoutedLog")
+    )
+}
+
+/// A `DefaultCallsite` expression named `name` with the given `target`,
+/// `level` and `fields` names.
+///
+/// `fields` are the message field, a second, never recorded, `sysfail` field,
+/// to mark the events of `sysfail` systems, and the context fields, see
+/// [`find_context_keys`].
+///
+/// `callsite` is an expression evaluating to a reference to the callsite
+/// itself, it is used as the `Identifier` of the metadata.
+fn default_callsite(
+    name: &TokenStream,
+    target: &TokenStream,
+    level: &TokenStream,
+    fields: &TokenStream,
+    callsite: &TokenStream,
+) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    quote! {{
+        static __SYSFAIL_META: #prefix::Metadata<'static> = #prefix::Metadata::new(
+            concat!("sysfail ", file!(), ":", line!()),
+            #target,
+            #level,
+            Some(file!()),
+            Some(line!()),
+            Some(#name),
+            #prefix::FieldSet::new(&[#fields], #prefix::Identifier(#callsite)),
+            #prefix::metadata::Kind::EVENT,
+        );
+        #prefix::DefaultCallsite::new(&__SYSFAIL_META)
+    }}
+}
+
+/// The type and value of the `__SYSFAIL_CALLSITE` static for `config`, and
+/// the expression of the callsite passed to the `Failure`.
+///
+/// All `Failure`s get a callsite, at the level of `Failure::LEVEL`, except
+/// when the `Failure` depends on the system's generics, since they can't be
+/// used in the static. Then only the logging `Failure`s of this crate get a
+/// callsite, their level is read from their `Lvl` type parameter.
+///
+/// When the `Failure` is a type parameter of the system, its level isn't known
+/// in the static, so it holds a callsite per level, like for `RoutedLog`,
+/// and the callsite of `Failure::LEVEL` is selected at runtime.
+///
+/// The callsites have a field per `context_keys`.
+fn callsite(
+    config: &FnConfig,
+    name: &TokenStream,
+    context_keys: &[TokenStream],
+) -> (TokenStream, TokenStream, TokenStream) {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let ret_type = &config.error_type;
+    let target = config
+        .target
+        .as_ref()
+        .map_or_else(|| name.clone(), |target| quote!(#target));
+    let field = config
+        .field
+        .as_ref()
+        .map_or_else(|| quote!("message"), |field| quote!(#field));
+    let fields = quote!(#field, "sysfail" #(, #context_keys)*);
+    let failure = if config.exclusive {
+        quote!(#prefix::ExclusiveFailure)
+    } else {
+        quote!(#prefix::Failure)
+    };
+    let callsite_ref = quote!(__SYSFAIL_CALLSITE.as_ref());
+    let single_level = match (log_type(ret_type), config.failure_generics) {
+        (_, FailureGenerics::TypeParam) => None,
+        (Some(log_type), _) if is_routed(log_type) => None,
+        (Some(log_type), _) => Some(log_level(log_type)),
+        (None, FailureGenerics::Independent) => Some(quote!(<#ret_type as #failure>::LEVEL)),
+        (None, FailureGenerics::Dependent) => {
+            return (quote!(#prefix::DefaultCallsite), quote!(None), callsite_ref);
+        }
+    };
+    if let Some(level) = single_level {
+        let callsite = quote!(match &__SYSFAIL_CALLSITE {
+            None => panic!(),
+            Some(c) => c,
+        });
+        let callsite = default_callsite(name, &target, &level, &fields, &callsite);
+        return (
+            quote!(#prefix::DefaultCallsite),
+            quote!(Some(#callsite)),
+            callsite_ref,
+        );
+    }
+    let levels = ["TRACE", "DEBUG", "INFO", "WARN", "ERROR"]
+        .into_iter()
+        .enumerate();
+    let callsites = levels.map(|(i, level)| {
+        let level = syn::Ident::new(level, proc_macro2::Span::call_site());
+        let callsite = quote!(match &__SYSFAIL_CALLSITE {
+            None => panic!(),
+            Some(c) => &c.0[#i],
+        });
+        let level = quote!(#prefix::Level::#level);
+        default_callsite(name, &target, &level, &fields, &callsite)
+    });
+    let callsite = quote!(Some(#prefix::LeveledCallsites([#(#callsites),*])));
+    let callsite_ref = if config.failure_generics == FailureGenerics::TypeParam {
+        quote!(__SYSFAIL_CALLSITE.as_ref().map(|c| {
+            c.get(<#ret_type as #failure>::LEVEL)
+        }))
+    } else {
+        callsite_ref
+    };
+    (quote!(#prefix::LeveledCallsites), callsite, callsite_ref)
+}
+
+// Local variables use `mixed_site` hygiene, so that they can't collide
+// with the user's own identifiers. Items can't be hygienic, so we give them
+// names unlikely to be used by the user.
+fn hygienic(name: &str) -> syn::Ident {
+    syn::Ident::new(name, proc_macro2::Span::mixed_site())
+}
+
+/// Statements defining `param_items` as `StatelessParam::detached()`, failing
+/// to compile if the `Failure::Param` of `ret_type` is not a `StatelessParam`.
+fn detached_params(ret_type: &syn::Type) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let param_items = hygienic("param_items");
+    quote! {
+        let #param_items = <
+            <#ret_type as #prefix::Failure>::Param as #prefix::StatelessParam
+        >::detached();
+    }
+}
+
+/// How the generated code calls the `Failure`.
+enum Handler {
+    /// Call `Failure` methods with the `param_items` variable, defined by
+    /// these statements.
+    Param(TokenStream),
+    /// Call `ExclusiveFailure` methods with this `&mut World` variable.
+    World(syn::Ident),
+}
+
+/// How `ty` depends on the generic parameters of `sig`.
+fn failure_generics(ty: &syn::Type, sig: &syn::Signature) -> FailureGenerics {
+    let is_type_param = match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path
+            .get_ident()
+            .is_some_and(|ident| sig.generics.type_params().any(|p| &p.ident == ident)),
+        _ => false,
+    };
+    let tokens = quote!(#ty);
+    let uses_param = |param: &syn::GenericParam| match param {
+        syn::GenericParam::Type(param) => mentions(&tokens, &param.ident.to_string()),
+        syn::GenericParam::Const(param) => mentions(&tokens, &param.ident.to_string()),
+        syn::GenericParam::Lifetime(param) => mentions(&tokens, &param.lifetime.ident.to_string()),
+    };
+    if is_type_param {
+        FailureGenerics::TypeParam
+    } else if sig.generics.params.iter().any(uses_param) {
+        FailureGenerics::Dependent
+    } else {
+        FailureGenerics::Independent
+    }
+}
+
+/// Whether the name of `ty` is `name`, such as `Configured`, which needs the
+/// `mode_ref` of the system.
+pub fn is_named(ty: &syn::Type, name: &str) -> bool {
+    let syn::Type::Path(syn::TypePath { path, .. }) = ty else {
+        return false;
+    };
+    path.segments.last().is_some_and(|last| last.ident == name)
+}
+
+/// Statements handling the `err` variable with `config.error_type` as `Failure`.
+fn handle_error(
+    config: &FnConfig,
+    name: &TokenStream,
+    handler: &Handler,
+    context_keys: &[TokenStream],
+) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let ret_type = &config.error_type;
+    let (callsite_ty, callsite, callsite_ref) = callsite(config, name, context_keys);
+    let param_items = hygienic("param_items");
+    let err = hygienic("err");
+    let result = hygienic("result");
+    let timer = hygienic("timer");
+    let (start, set_elapsed) = (hygienic("start"), hygienic("set_elapsed"));
+    let set_elapsed = quote! {
+        let mut #err = #err;
+        if let Some((#start, #set_elapsed)) = #timer {
+            #set_elapsed(&mut #err, #start.elapsed());
+        }
+    };
+    let count_runs = hygienic("count_runs");
+    let set_runs = quote! {
+        let mut #err = #err;
+        if let Some(#count_runs) = #count_runs {
+            #count_runs(&mut #err, &__SYSFAIL_RUNS);
+        }
+    };
+    let set_mode = config
+        .mode_ref
+        .as_ref()
+        .filter(|_| is_named(ret_type, "Configured"));
+    let set_mode = set_mode.map(|path| {
+        quote_spanned! {path.span()=>
+            let mut #err = #err;
+            #prefix::Configure::set_mode(&mut #err, &#path);
+        }
+    });
+    let on_error = config.on_error.as_ref().map(|path| {
+        let on_error = hygienic("on_error");
+        quote_spanned! {path.span()=>
+            let #on_error: fn(&#ret_type) = #path;
+            #on_error(&#err);
+        }
+    });
+    // `cfg!(test)` of the user's crate, for `LogOrPanic`
+    let cfg_test = hygienic("_cfg_test");
+    let handle = match handler {
+        Handler::Param(get_params) => quote! {
+            #get_params
+            let #result = #prefix::Failure::handle_error_fallible(#err, #param_items, #callsite_ref);
+        },
+        Handler::World(world) => quote! {
+            let #result = #prefix::ExclusiveFailure::handle_exclusive(#err, #world, #callsite_ref);
+        },
+    };
+    quote! {
+        static __SYSFAIL_CALLSITE: Option<#callsite_ty> = #callsite;
+        #set_elapsed
+        #set_runs
+        #set_mode
+        #on_error
+        let #cfg_test = #prefix::CfgTest::enter(cfg!(test));
+        #handle
+        if let Err(#err) = #result {
+            #prefix::handler_failed(#name, &*#err);
+        }
+    }
+}
+
+/// Statements calling `on_success` for `ret_type`.
+fn handle_success(ret_type: &syn::Type, handler: &Handler) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let param_items = hygienic("param_items");
+    match handler {
+        Handler::Param(get_params) => quote! {
+            #get_params
+            <#ret_type as #prefix::Failure>::on_success(#param_items);
+        },
+        Handler::World(world) => quote! {
+            <#ret_type as #prefix::ExclusiveFailure>::on_success_exclusive(#world);
+        },
+    }
+}
+
+/// The statements of a system body.
+///
+/// When the last statement is an expression without a trailing `;`, it is
+/// handled like if it was followed by `?` when it is a `Result<(), E>`, and
+/// discarded otherwise, see `Trailing`.
+///
+/// With `forward`, the last expression is the value returned by the system,
+/// so the statements are left untouched.
+///
+/// With `Ignore`, errors are discarded without converting them, see
+/// [`IgnoreErrors`].
+fn body_statements(ret_type: &syn::Type, stmts: &[syn::Stmt], forward: bool) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let mut stmts = stmts.to_vec();
+    let is_ignore = is_named(ret_type, "Ignore");
+    if is_ignore {
+        for stmt in &mut stmts {
+            IgnoreErrors.visit_stmt_mut(stmt);
+        }
+    }
+    if forward {
+        return quote!(#(#stmts)*);
+    }
+    let convert = if is_ignore {
+        quote!(#prefix::IgnoreErr::ignore_err)
+    } else {
+        quote!(#prefix::into_failure::<#ret_type, _, _>)
+    };
+    match stmts.split_last() {
+        Some((syn::Stmt::Expr(last, None), init)) if !is_block_like(last) => {
+            let trailing = hygienic("trailing");
+            // Spanned, so that `#[track_caller]` points to the expression
+            let into_result = quote_spanned! {last.span()=>
+                {
+                    #[allow(unused_imports)]
+                    use #prefix::{TrailingResult as _, TrailingValue as _};
+                    let #trailing = #prefix::Trailing::<#ret_type, _>(#last, ::core::marker::PhantomData);
+                    #convert(#trailing.into_result())?
+                }
+            };
+            quote!(#(#init)* #into_result)
+        }
+        _ => quote!(#(#stmts)*),
+    }
+}
+
+/// Rewrites the bare `return;` of a system body into `return Ok(());`, since
+/// the body becomes the body of a closure returning `Result<(), E>`.
+///
+/// `return value;` becomes `return SystemReturn::into_result(value);`, so
+/// `value` can be `()` or a `Result<(), E>`, like the trailing expression.
+/// This is the case of the `return` added by attribute macros expanded before
+/// `#[sysfail]`, such as `#[tracing::instrument]`. `return Ok(..);`,
+/// `return Err(..);` and `return expr?;` are left as is, since `into_result`
+/// would make their types ambiguous. With the `forward` option, `return value;`
+/// becomes `return Ok(value);`.
+/// Nested closures, `async` blocks and items are skipped, since their `return`s
+/// don't return from the system, so are `return`s in macro invocations, which
+/// can't be parsed.
+struct BareReturns {
+    forward: bool,
+}
+impl VisitMut for BareReturns {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        match expr {
+            syn::Expr::Closure(_) | syn::Expr::Async(_) => {}
+            syn::Expr::Return(syn::ExprReturn { expr: returned @ None, .. }) => {
+                *returned = Some(parse_quote!(::core::result::Result::Ok(())));
+            }
+            syn::Expr::Return(syn::ExprReturn { expr: Some(returned), .. }) if self.forward => {
+                self.visit_expr_mut(returned);
+                **returned = parse_quote!(::core::result::Result::Ok(#returned));
+            }
+            syn::Expr::Return(syn::ExprReturn { expr: Some(returned), .. }) => {
+                self.visit_expr_mut(returned);
+                if !is_result_variant(returned) {
+                    **returned = parse_quote!(
+                        ::bevy_mod_sysfail::__macro::SystemReturn::into_result(#returned)
+                    );
+                }
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+/// Whether `expr` is an `Ok(..)` or `Err(..)` call, or a `?` expression,
+/// such as `Err(error)?`.
+fn is_result_variant(expr: &syn::Expr) -> bool {
+    let syn::Expr::Call(syn::ExprCall { func, .. }) = expr else {
+        return matches!(expr, syn::Expr::Try(_));
+    };
+    let syn::Expr::Path(syn::ExprPath { path, .. }) = &**func else {
+        return false;
+    };
+    path.segments
+        .last()
+        .is_some_and(|last| last.ident == "Ok" || last.ident == "Err")
+}
+
+/// Rewrites the `expr?` of a system body into `TryValue::into_result(expr)?`,
+/// so that `?` on `Option`s returns a `NoneError`, for the `none_error` option.
+///
+/// The call is spanned at `expr`, so that the `NoneError` location is the
+/// location of `expr`. Like with [`BareReturns`], nested closures, `async`
+/// blocks, items and macro invocations are skipped.
+struct NoneErrors;
+impl VisitMut for NoneErrors {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        match expr {
+            syn::Expr::Closure(_) | syn::Expr::Async(_) => {}
+            syn::Expr::Try(syn::ExprTry { expr: tried, .. }) => {
+                self.visit_expr_mut(tried);
+                // The path is spanned too, since it is the span of the call
+                **tried = parse_quote_spanned! {tried.span()=>
+                    ::bevy_mod_sysfail::__macro::TryValue::into_result(#tried)
+                };
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
 
-fn is_log(ty: &syn::Type) -> bool {
-    matches!(ty, syn::Type::Path(syn::TypePath{path, ..})
-        if path.segments.last().is_some_and(|p| p.ident.to_string().contains("Log"))
+/// Rewrites the `expr?` of a system body into `Result::map_err(expr, map)?`,
+/// for the `map = path` option.
+///
+/// The call is spanned at `map`, so that a `map` with the wrong signature is
+/// reported at the option. This runs after [`NoneErrors`], so that `map`
+/// also receives the `NoneError`s. Like with [`BareReturns`], nested closures,
+/// `async` blocks, items and macro invocations are skipped.
+struct MapErrors<'a> {
+    map: &'a syn::Path,
+}
+impl VisitMut for MapErrors<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        match expr {
+            syn::Expr::Closure(_) | syn::Expr::Async(_) => {}
+            syn::Expr::Try(syn::ExprTry { expr: tried, .. }) => {
+                self.visit_expr_mut(tried);
+                let map = self.map;
+                **tried = parse_quote_spanned! {map.span()=>
+                    ::core::result::Result::map_err(#tried, #map)
+                };
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+/// Rewrites the `expr?` of a system body into `into_failure::<FailureType, _, _>(expr)?`,
+/// so that a missing `From` implementation for the failure type is reported
+/// at the failure type of the attribute, as well as at `expr`.
+///
+/// The call is spanned at `expr`, like with [`NoneErrors`], so that
+/// `#[track_caller]` conversions record the location of `expr`. This runs
+/// after [`MapErrors`], so that the output of `map` is converted.
+struct IntoFailures<'a> {
+    ret_type: &'a syn::Type,
+}
+impl VisitMut for IntoFailures<'_> {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        match expr {
+            syn::Expr::Closure(_) | syn::Expr::Async(_) => {}
+            syn::Expr::Try(syn::ExprTry { expr: tried, .. }) => {
+                self.visit_expr_mut(tried);
+                let ret_type = self.ret_type;
+                **tried = parse_quote_spanned! {tried.span()=>
+                    ::bevy_mod_sysfail::__macro::into_failure::<#ret_type, _, _>(#tried)
+                };
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+/// Rewrites the `expr?` of a system body into `IgnoreErr::ignore_err(expr)?`,
+/// so that `Ignore` doesn't need a `From` implementation for the error type.
+///
+/// Like with [`BareReturns`], nested closures, `async` blocks, items and macro
+/// invocations are skipped.
+struct IgnoreErrors;
+impl VisitMut for IgnoreErrors {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        match expr {
+            syn::Expr::Closure(_) | syn::Expr::Async(_) => {}
+            syn::Expr::Try(syn::ExprTry { expr: tried, .. }) => {
+                self.visit_expr_mut(tried);
+                let prefix = quote!(::bevy_mod_sysfail::__macro);
+                **tried = parse_quote!(#prefix::IgnoreErr::ignore_err(#tried));
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+fn is_block_like(expr: &syn::Expr) -> bool {
+    use syn::Expr::{Block, ForLoop, If, Loop, Match, Unsafe, While};
+    matches!(
+        expr,
+        Block(_) | ForLoop(_) | If(_) | Loop(_) | Match(_) | Unsafe(_) | While(_)
     )
 }
 
-pub fn sysfail(config: &FnConfig, function: syn::ItemFn) -> TokenStream {
+/// Whether `tokens` contains the `name` identifier.
+fn mentions(tokens: &TokenStream, name: &str) -> bool {
+    tokens.clone().into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => ident == name,
+        proc_macro2::TokenTree::Group(group) => mentions(&group.stream(), name),
+        _ => false,
+    })
+}
+
+/// Add the keys recorded by the `sysfail_context!` and `sysfail_tag!`
+/// invocations of `tokens` to `keys`, if not already in it.
+///
+/// They are the context fields of the callsites, recorded as fields rather
+/// than appended to the message, see `dispatch_message`. Invocations not
+/// written in the body, such as the ones expanded from other macros, are not
+/// seen, their keys are appended to the message.
+fn find_context_keys(tokens: &TokenStream, keys: &mut Vec<TokenStream>) {
+    use proc_macro2::TokenTree::{Group, Ident, Literal, Punct};
+    let is_punct =
+        |token: &proc_macro2::TokenTree, c| matches!(token, Punct(p) if p.as_char() == c);
+    let push = |keys: &mut Vec<TokenStream>, key: TokenStream| {
+        if keys.iter().all(|k| k.to_string() != key.to_string()) {
+            keys.push(key);
+        }
+    };
+    let tokens: Vec<_> = tokens.clone().into_iter().collect();
+    for (i, token) in tokens.iter().enumerate() {
+        match (token, tokens.get(i + 1), tokens.get(i + 2)) {
+            (Ident(ident), Some(bang), Some(Group(args))) if is_punct(bang, '!') => {
+                let args = args.stream();
+                if ident == "sysfail_tag" {
+                    push(keys, quote!(::core::stringify!(#args)));
+                } else if ident == "sysfail_context" {
+                    let args: Vec<_> = args.into_iter().collect();
+                    for window in args.windows(3) {
+                        if let [key @ Literal(_), eq, gt] = window {
+                            if is_punct(eq, '=') && is_punct(gt, '>') {
+                                push(keys, quote!(#key));
+                            }
+                        }
+                    }
+                }
+            }
+            (Group(group), _, _) => find_context_keys(&group.stream(), keys),
+            _ => {}
+        }
+    }
+}
+
+/// Count the runs of the system in the `__SYSFAIL_RUNS` static, if the
+/// `ret_type` `Failure` is a `CountRuns`, see `Probe`.
+///
+/// `count_runs` is the `CountRuns::set_runs` method of `ret_type`, if any.
+fn count_runs(ret_type: &syn::Type) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let count_runs = hygienic("count_runs");
+    quote! {
+        static __SYSFAIL_RUNS: #prefix::SystemRuns = #prefix::SystemRuns::new();
+        let #count_runs = {
+            #[allow(unused_imports)]
+            use #prefix::{NoRuns as _, ProbeRuns as _};
+            #prefix::Probe::<#ret_type>(::core::marker::PhantomData).count_runs()
+        };
+        if #count_runs.is_some() {
+            __SYSFAIL_RUNS.advance();
+        }
+    }
+}
+
+/// Start timing the system in the `timer` variable, if the `ret_type`
+/// `Failure` is `Timed`, see `Probe`.
+///
+/// `timer` is the start of the system and the `Timed::set_elapsed` method of
+/// `ret_type`, if any.
+fn start_timer(ret_type: &syn::Type) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let timer = hygienic("timer");
+    quote! {
+        let #timer = {
+            #[allow(unused_imports)]
+            use #prefix::{NoTimer as _, ProbeTimed as _};
+            #prefix::Probe::<#ret_type>(::core::marker::PhantomData).start_timer()
+        };
+    }
+}
+
+/// Define the `context` variable, and the `sysfail_context!` and
+/// `sysfail_tag!` macros recording values in it.
+fn context_macros(context: &syn::Ident) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let context_ref = hygienic("context_ref");
+    quote! {
+        let mut #context = #prefix::Context::default();
+        let #context_ref = &mut #context;
+        #[allow(unused_macros)]
+        macro_rules! sysfail_context {
+            ($($key:literal => $value:expr),+ $(,)?) => {
+                $( #context_ref.record($key, &$value); )+
+            };
+        }
+        #[allow(unused_macros)]
+        macro_rules! sysfail_tag {
+            ($id:expr) => {
+                #context_ref.record(::core::stringify!($id), &$id);
+            };
+        }
+    }
+}
+
+/// Run `body` in a closure returning `Result<(), E>`, and handle
+/// the error with `E` as `Failure`, where `E` is `config.error_type`.
+/// When `body` succeeds, call `Failure::on_success`.
+///
+/// `handler` is how to call the `Failure` methods of `E`.
+///
+/// The `sysfail_handle!` macro is defined for `body`, to handle errors
+/// without returning, when the `Failure::Param` of `E` is a `StatelessParam`.
+/// It does nothing when the `enabled` variable, if any, is `false`.
+fn handle_body(
+    config: &FnConfig,
+    name: &TokenStream,
+    closure: &TokenStream,
+    body: &TokenStream,
+    handler: &Handler,
+    output: Option<&syn::Type>,
+    enabled: Option<&syn::Ident>,
+) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let ret_type = &config.error_type;
+    let inner_system = hygienic("inner_system");
+    let err = hygienic("err");
+    let run_system = if config.flags.catch_panic {
+        let payload = hygienic("payload");
+        quote! {
+            ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| #inner_system()))
+                .unwrap_or_else(|#payload| ::core::result::Result::Err(
+                    ::core::convert::From::from(#prefix::CaughtPanic::new(&*#payload))
+                ))
+        }
+    } else {
+        quote!(#inner_system())
+    };
+    let mut context_keys = Vec::new();
+    find_context_keys(body, &mut context_keys);
+    let mut handle_returned = handle_error(config, name, handler, &context_keys);
+    let handle_success = handle_success(ret_type, handler);
+    let inline_handler = Handler::Param(detached_params(ret_type));
+    let mut handle_inline = handle_error(config, name, &inline_handler, &context_keys);
+    if let Some(enabled) = enabled {
+        handle_inline = quote!(if #enabled { #handle_inline });
+    }
+    let uses_context = mentions(body, "sysfail_context") || mentions(body, "sysfail_tag");
+    let context = uses_context.then(|| {
+        let context = hygienic("context");
+        handle_returned = quote!(#context.scope(|| { #handle_returned }););
+        context_macros(&context)
+    });
+    let count_runs = count_runs(ret_type);
+    let start_timer = start_timer(ret_type);
+    let run_body = output.map_or_else(
+        || {
+            quote! {
+                let mut #inner_system = #closure -> ::core::result::Result<(), #ret_type> {
+                    #body;
+                    return ::core::result::Result::Ok(());
+                };
+                match #run_system {
+                    ::core::result::Result::Err(#err) => {
+                        #handle_returned
+                    }
+                    ::core::result::Result::Ok(()) => {
+                        #handle_success
+                    }
+                }
+            }
+        },
+        |output| {
+            let value = hygienic("value");
+            quote! {
+                let mut #inner_system = #closure -> ::core::result::Result<#output, #ret_type> {
+                    ::core::result::Result::Ok({ #body })
+                };
+                match #run_system {
+                    ::core::result::Result::Err(#err) => {
+                        #handle_returned
+                        ::core::default::Default::default()
+                    }
+                    ::core::result::Result::Ok(#value) => {
+                        #handle_success
+                        #value
+                    }
+                }
+            }
+        },
+    );
+    quote! {
+        #count_runs
+        #start_timer
+        #context
+        // Returns from the body closure, so the error handler isn't called
+        #[allow(unused_macros)]
+        macro_rules! bail_ok {
+            () => {
+                return ::core::result::Result::Ok(::core::default::Default::default())
+            };
+            ($value:expr) => {
+                return ::core::result::Result::Ok($value)
+            };
+        }
+        #[allow(unused_macros)]
+        macro_rules! sysfail_handle {
+            ($error:expr) => {{
+                let #err: #ret_type = ::core::convert::From::from($error);
+                #handle_inline
+            }};
+        }
+        #run_body
+    }
+}
+
+/// Replace the `&mut World` parameter of `sig` by the returned identifier.
+///
+/// Also returns a statement binding the original pattern to the world, so that
+/// the world can still be used after running the system body. The body closure
+/// captures this reborrow, which ends when the closure returns, so the error
+/// handler can then use the world again.
+fn exclusive_world(sig: &mut syn::Signature) -> syn::Result<(syn::Ident, TokenStream)> {
+    let is_world = |ty: &syn::Type| {
+        matches!(ty, syn::Type::Reference(syn::TypeReference { mutability: Some(_), elem, .. })
+            if matches!(&**elem, syn::Type::Path(p) if p.path.segments.last().is_some_and(|s| s.ident == "World"))
+        )
+    };
+    let world_param = sig.inputs.iter_mut().find_map(|input| match input {
+        syn::FnArg::Typed(pat) if is_world(&pat.ty) => Some(pat),
+        _ => None,
+    });
+    let Some(world_param) = world_param else {
+        let msg = "#[exclusive_sysfail] systems must have a `&mut World` parameter";
+        return Err(syn::Error::new_spanned(&sig.inputs, msg));
+    };
+    let world = hygienic("sysfail_world");
+    let pat = std::mem::replace(&mut *world_param.pat, parse_quote!(#world));
+    let ty = &world_param.ty;
+    Ok((world.clone(), quote!(let #pat: #ty = &mut *#world;)))
+}
+
+/// The `#[sysfail]` system for `function`, with the given `config`.
+///
+/// This only depends on its arguments, parse `config` with
+/// [`FnConfig::parse_attrs`].
+pub fn generate(config: &FnConfig, function: syn::ItemFn) -> TokenStream {
+    let set = config.set.as_ref().map(|set| {
+        // The set only exists if the system exists
+        let cfgs: Vec<_> = function
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .collect();
+        system_set(set, &function.vis, &cfgs)
+    });
+    let system = sysfail_modes(config, function);
+    quote!(#set #system)
+}
+
+/// A `SystemSet` unit struct named `set`, with the `cfgs` attributes of the
+/// system.
+///
+/// This is what `#[derive(SystemSet)]` generates, we don't use the derive
+/// since it requires the user to depend on `bevy_ecs` or `bevy` directly.
+fn system_set(set: &syn::Ident, vis: &syn::Visibility, cfgs: &[&syn::Attribute]) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    quote! {
+        #(#cfgs)*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis struct #set;
+        #(#cfgs)*
+        impl #prefix::SystemSet for #set {
+            fn dyn_clone(&self) -> ::std::boxed::Box<dyn #prefix::SystemSet> {
+                ::std::boxed::Box::new(*self)
+            }
+            fn as_dyn_eq(&self) -> &dyn #prefix::DynEq {
+                self
+            }
+            fn dyn_hash(&self, mut state: &mut dyn ::std::hash::Hasher) {
+                ::std::hash::Hash::hash(&::std::any::TypeId::of::<Self>(), &mut state);
+                ::std::hash::Hash::hash(self, &mut state);
+            }
+        }
+    }
+}
+
+fn sysfail_modes(config: &FnConfig, function: syn::ItemFn) -> TokenStream {
+    if config.debug.is_none() && config.release.is_none() {
+        return sysfail_single(config, function);
+    }
+    // Generate the system twice, each with a different `Failure` and `cfg`,
+    // so that only the `Failure` of the current build mode is compiled.
+    let with_error = |error_type: &Option<syn::Type>| {
+        let mut config = config.clone();
+        config.error_type = error_type.clone().unwrap_or(config.error_type);
+        config.debug = None;
+        config.release = None;
+        sysfail_single(&config, function.clone())
+    };
+    let debug = with_error(&config.debug);
+    let release = with_error(&config.release);
+    quote! {
+        #[cfg(debug_assertions)]
+        #debug
+        #[cfg(not(debug_assertions))]
+        #release
+    }
+}
+fn sysfail_single(config: &FnConfig, function: syn::ItemFn) -> TokenStream {
     match sysfail_inner(config, function) {
         Ok(token_stream) => token_stream,
         Err(syn_error) => syn_error.into_compile_error(),
     }
 }
 fn sysfail_inner(config: &FnConfig, mut function: syn::ItemFn) -> syn::Result<TokenStream> {
-    if !matches!(function.sig.output, syn::ReturnType::Default) {
-        return Err(syn::Error::new_spanned(function.sig.output, QUICK_MSG));
+    let failure_generics = failure_generics(&config.error_type, &function.sig);
+    let config = &FnConfig { failure_generics, ..config.clone() };
+    let output = match &function.sig.output {
+        syn::ReturnType::Type(_, output) if config.flags.forward => Some((**output).clone()),
+        syn::ReturnType::Default if config.flags.forward => {
+            let msg = "`forward` systems must have a return type";
+            return Err(syn::Error::new_spanned(&function.sig, msg));
+        }
+        syn::ReturnType::Default => None,
+        output @ syn::ReturnType::Type(..) => {
+            return Err(syn::Error::new_spanned(output, QUICK_MSG));
+        }
+    };
+    if let Some(asyncness) = function.sig.asyncness {
+        return Err(syn::Error::new_spanned(asyncness, ASYNC_MSG));
     }
+    let exclusive_world = config
+        .exclusive
+        .then(|| exclusive_world(&mut function.sig))
+        .transpose()?;
+    let let_world = exclusive_world
+        .as_ref()
+        .map(|(_, let_world)| let_world.clone());
     let ret_type = &config.error_type;
+    BareReturns { forward: config.flags.forward }.visit_block_mut(&mut function.block);
+    if config.flags.none_error {
+        NoneErrors.visit_block_mut(&mut function.block);
+    }
+    if let Some(map) = &config.map {
+        MapErrors { map }.visit_block_mut(&mut function.block);
+    }
+    if !is_named(ret_type, "Ignore") {
+        IntoFailures { ret_type }.visit_block_mut(&mut function.block);
+    }
     let body = &function.block.stmts;
     let vis = &function.vis;
     let fn_ident = &function.sig.ident;
@@ -45,54 +932,343 @@ fn sysfail_inner(config: &FnConfig, mut function: syn::ItemFn) -> syn::Result<To
         function.sig.inputs.push_punct(syn::token::Comma::default());
     }
     let params = &function.sig.inputs;
+    let output_ty = &function.sig.output;
     let params_gen = &function.sig.generics.params;
     let where_gen = &function.sig.generics.where_clause;
     let attrs = &function.attrs;
+    // `Ignore` systems are as cheap as the body, so let other crates inline them
+    let has_inline = attrs.iter().any(|attr| attr.path().is_ident("inline"));
+    let inline = (is_named(ret_type, "Ignore") && !has_inline).then(|| quote!(#[inline]));
     let prefix = quote!(::bevy_mod_sysfail::__macro);
-    let callsite = if is_log(ret_type) {
-        quote! {Some({
-            static META: #prefix::Metadata<'static> = #prefix::Metadata::new(
-                concat!(file!(), ":", line!()),
-                concat!(module_path!(), "::", stringify!(#fn_ident)),
-                <#ret_type as #prefix::Failure>::LEVEL,
-                Some(file!()),
-                Some(line!()),
-                Some(concat!(module_path!(), "::", stringify!(#fn_ident))),
-                #prefix::FieldSet::new(&["message"], #prefix::Identifier(match &CALLSITE {
-                    None => panic!(),
-                    Some(c) => c,
-                })),
-                #prefix::metadata::Kind::EVENT,
-            );
-            #prefix::DefaultCallsite::new(&META)
-        })}
-    } else {
-        quote!(None)
+    let sysfail_params = hygienic("sysfail_params");
+    let param_items = hygienic("param_items");
+    // The lifetimes of `StaticSystemParam` are elided, so they are fresh
+    // lifetimes, distinct from the lifetime parameters of the system.
+    let extra_param = (!config.exclusive).then(|| {
+        quote!(#sysfail_params: #prefix::StaticSystemParam<<#ret_type as #prefix::Failure>::Param>)
+    });
+    // `sysfail_handle!` can't access the `World`, so we read `SysfailEnabled`
+    // before running the body.
+    let uses_handle = mentions(&quote!(#(#body)*), "sysfail_handle");
+    let enabled = uses_handle.then(|| hygienic("sysfail_enabled"));
+    let sysfail_hooks = hygienic("sysfail_hooks");
+    let hooks_param = (uses_handle && !config.exclusive)
+        .then(|| quote!(, #sysfail_hooks: #prefix::SysfailHooks<'_>));
+    let read_enabled = enabled.as_ref().map(|enabled| {
+        exclusive_world.as_ref().map_or_else(
+            || quote!(let #enabled = #sysfail_hooks.enabled();),
+            |(world, _)| {
+                quote! {
+                    let #enabled =
+                        <#prefix::SysfailHooks as #prefix::StatelessParam>::from_world(&*#world)
+                            .enabled();
+                }
+            },
+        )
+    });
+    let handler = match exclusive_world {
+        Some((world, _)) => Handler::World(world),
+        None => Handler::Param(quote!(let #param_items = #sysfail_params.into_inner();)),
     };
-    let extra_param = (!config.exclusive) .then(||
-        quote!(__sysfail_params: #prefix::StaticSystemParam<<#ret_type as #prefix::Failure>::Param>)
+    let name = quote!(concat!(module_path!(), "::", stringify!(#fn_ident)));
+    let register_site = register_site(config, &name);
+    let handle_body = handle_body(
+        config,
+        &name,
+        &quote!(move ||),
+        &body_statements(ret_type, body, config.flags.forward),
+        &handler,
+        output.as_ref(),
+        enabled.as_ref(),
     );
-    let check_exclusive = if config.exclusive {
-        quote! {
-            fn Failure_has_UnitParam<F: Failure<Param=()>>() -> F::Param {}
-            let param_items = Failure_has_UnitParam::<#ret_type>();
-        }
-    } else {
-        quote!(let param_items = __sysfail_params.into_inner();)
-    };
     Ok(quote! {
         #(#attrs)*
-        #vis fn #fn_ident <#params_gen> (#params #extra_param) #where_gen {
-            use ::bevy_mod_sysfail::Failure;
-            let mut inner_system = move || -> ::core::result::Result<(), #ret_type> {
-                #(#body)*;
-                return ::core::result::Result::Ok(());
-            };
-            if let Err(err) = inner_system() {
-                static CALLSITE: Option<#prefix::DefaultCallsite> = #callsite;
-                #check_exclusive
-                err.handle_error(param_items, CALLSITE.as_ref());
-            }
+        #inline
+        #vis fn #fn_ident <#params_gen> (#params #extra_param #hooks_param) #output_ty #where_gen {
+            #register_site
+            #read_enabled
+            #let_world
+            #handle_body
         }
     })
 }
+
+/// Register the `SysfailSite` of the system named `name`, for `sysfail_sites`.
+///
+/// Without the `registry` feature, this is empty.
+#[cfg(not(feature = "registry"))]
+fn register_site(_: &FnConfig, _: &TokenStream) -> TokenStream {
+    TokenStream::new()
+}
+
+/// Register the `SysfailSite` of the system named `name`, for `sysfail_sites`.
+#[cfg(feature = "registry")]
+fn register_site(config: &FnConfig, name: &TokenStream) -> TokenStream {
+    let prefix = quote!(::bevy_mod_sysfail::__macro);
+    let ret_type = &config.error_type;
+    let target = config
+        .target
+        .as_ref()
+        .map_or_else(|| name.clone(), |target| quote!(#target));
+    quote! {
+        #[#prefix::__linkme::distributed_slice(#prefix::SYSFAIL_SITES)]
+        #[linkme(crate = #prefix::__linkme)]
+        static __SYSFAIL_SITE: #prefix::SysfailSite = #prefix::SysfailSite::new(
+            #name,
+            #target,
+            file!(),
+            line!(),
+            stringify!(#ret_type),
+        );
+    }
+}
+
+/// The arguments of `sysfail_block!`: `param, FailureType, { body }`.
+pub struct BlockConfig {
+    param: syn::Expr,
+    config: FnConfig,
+    block: syn::Block,
+}
+impl Parse for BlockConfig {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let param = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let config = FnConfig { error_type: input.parse()?, ..FnConfig::default() };
+        input.parse::<syn::Token![,]>()?;
+        let block = input.parse()?;
+        input.parse::<Option<syn::Token![,]>>()?;
+        Ok(Self { param, config, block })
+    }
+}
+
+pub fn sysfail_block(config: &BlockConfig) -> TokenStream {
+    let BlockConfig { param, config, block } = config;
+    let body = &block.stmts;
+    let param_items = hygienic("param_items");
+    let handler = Handler::Param(quote!(let #param_items = #param;));
+    let name = quote!(module_path!());
+    let body = body_statements(&config.error_type, body, false);
+    let handle_body = handle_body(config, &name, &quote!(||), &body, &handler, None, None);
+    quote!({ #handle_body })
+}
+
+fn is_sysfail_attr(attr: &syn::Attribute) -> bool {
+    let last = attr.path().segments.last();
+    last.is_some_and(|p| p.ident == "sysfail" || p.ident == "exclusive_sysfail")
+}
+fn is_skip_attr(attr: &syn::Attribute) -> bool {
+    let is_skip = || {
+        attr.parse_args::<syn::Ident>()
+            .is_ok_and(|ident| ident == "skip")
+    };
+    attr.path().is_ident("sysfail") && is_skip()
+}
+
+pub fn sysfail_mod(config: &FnConfig, mut module: syn::ItemMod) -> TokenStream {
+    if let Some(set) = &config.set {
+        let msg =
+            "`set` can't be used with #[sysfail_mod], it would define the set once per system";
+        return syn::Error::new_spanned(set, msg).into_compile_error();
+    }
+    let Some((_, items)) = &mut module.content else {
+        let msg = "#[sysfail_mod] only works on inline modules, with a `{ ... }` body.";
+        return syn::Error::new_spanned(module, msg).into_compile_error();
+    };
+    for item in items {
+        let syn::Item::Fn(function) = item else {
+            continue;
+        };
+        if function.attrs.iter().any(is_skip_attr) {
+            function.attrs.retain(|attr| !is_skip_attr(attr));
+        } else if !function.attrs.iter().any(is_sysfail_attr) {
+            *item = syn::Item::Verbatim(generate(config, function.clone()));
+        }
+    }
+    quote!(#module)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Flags;
+
+    fn assert_generates(config: &FnConfig, function: syn::ItemFn, expected: &TokenStream) {
+        assert_eq!(generate(config, function).to_string(), expected.to_string());
+    }
+
+    #[test]
+    fn return_type() {
+        let function = parse_quote!(
+            fn system() -> u32 {
+                0
+            }
+        );
+        let expected = quote!(::core::compile_error! { #QUICK_MSG });
+        assert_generates(&FnConfig::default(), function, &expected);
+    }
+
+    #[test]
+    fn async_system() {
+        let function = parse_quote!(
+            async fn system() {}
+        );
+        let expected = quote!(::core::compile_error! { #ASYNC_MSG });
+        assert_generates(&FnConfig::default(), function, &expected);
+    }
+
+    #[test]
+    fn forward_without_return_type() {
+        let config = FnConfig {
+            flags: Flags { forward: true, ..Flags::default() },
+            ..FnConfig::default()
+        };
+        let msg = "`forward` systems must have a return type";
+        let expected = quote!(::core::compile_error! { #msg });
+        assert_generates(
+            &config,
+            parse_quote!(
+                fn system() {}
+            ),
+            &expected,
+        );
+    }
+
+    #[test]
+    fn ignore_system() {
+        let config = FnConfig {
+            error_type: parse_quote!(Ignore),
+            ..FnConfig::default()
+        };
+        let function = parse_quote!(
+            fn system() {
+                let () = Err(())?;
+            }
+        );
+        let name = quote!(concat!(module_path!(), "::", stringify!(system)));
+        let site = register_site(&config, &name);
+        let (callsite_ty, callsite, _) = callsite(&config, &name, &[]);
+        let prefix = quote!(::bevy_mod_sysfail::__macro);
+        let set_runs = quote! {
+            let mut err = err;
+            if let Some((start, set_elapsed)) = timer {
+                set_elapsed(&mut err, start.elapsed());
+            }
+            let mut err = err;
+            if let Some(count_runs) = count_runs {
+                count_runs(&mut err, &__SYSFAIL_RUNS);
+            }
+        };
+        let expected = quote! {
+            #[inline]
+            fn system<>(sysfail_params: #prefix::StaticSystemParam<<Ignore as #prefix::Failure>::Param>) {
+                #site
+                static __SYSFAIL_RUNS: #prefix::SystemRuns = #prefix::SystemRuns::new();
+                let count_runs = {
+                    #[allow(unused_imports)]
+                    use #prefix::{NoRuns as _, ProbeRuns as _};
+                    #prefix::Probe::<Ignore>(::core::marker::PhantomData).count_runs()
+                };
+                if count_runs.is_some() {
+                    __SYSFAIL_RUNS.advance();
+                }
+                let timer = {
+                    #[allow(unused_imports)]
+                    use #prefix::{NoTimer as _, ProbeTimed as _};
+                    #prefix::Probe::<Ignore>(::core::marker::PhantomData).start_timer()
+                };
+                #[allow(unused_macros)]
+                macro_rules! bail_ok {
+                    () => { return ::core::result::Result::Ok(::core::default::Default::default()) };
+                    ($value:expr) => { return ::core::result::Result::Ok($value) };
+                }
+                #[allow(unused_macros)]
+                macro_rules! sysfail_handle {
+                    ($error:expr) => {{
+                        let err: Ignore = ::core::convert::From::from($error);
+                        static __SYSFAIL_CALLSITE: Option<#callsite_ty> = #callsite;
+                        #set_runs
+                        let _cfg_test = #prefix::CfgTest::enter(cfg!(test));
+                        let param_items =
+                            < <Ignore as #prefix::Failure>::Param as #prefix::StatelessParam>::detached();
+                        let result = #prefix::Failure::handle_error_fallible(
+                            err,
+                            param_items,
+                            __SYSFAIL_CALLSITE.as_ref()
+                        );
+                        if let Err(err) = result {
+                            #prefix::handler_failed(#name, &*err);
+                        }
+                    }};
+                }
+                let mut inner_system = move || -> ::core::result::Result<(), Ignore> {
+                    let () = #prefix::IgnoreErr::ignore_err(Err(()))?;
+                    ;
+                    return ::core::result::Result::Ok(());
+                };
+                match inner_system() {
+                    ::core::result::Result::Err(err) => {
+                        static __SYSFAIL_CALLSITE: Option<#callsite_ty> = #callsite;
+                        #set_runs
+                        let _cfg_test = #prefix::CfgTest::enter(cfg!(test));
+                        let param_items = sysfail_params.into_inner();
+                        let result = #prefix::Failure::handle_error_fallible(
+                            err,
+                            param_items,
+                            __SYSFAIL_CALLSITE.as_ref()
+                        );
+                        if let Err(err) = result {
+                            #prefix::handler_failed(#name, &*err);
+                        }
+                    }
+                    ::core::result::Result::Ok(()) => {
+                        let param_items = sysfail_params.into_inner();
+                        <Ignore as #prefix::Failure>::on_success(param_items);
+                    }
+                }
+            }
+        };
+        assert_generates(&config, function, &expected);
+    }
+
+    #[test]
+    fn set() {
+        let function: syn::ItemFn = parse_quote! {
+            #[cfg(feature = "foo")]
+            pub fn system() {}
+        };
+        let set = parse_quote!(SystemSet);
+        let config = FnConfig { set: Some(set), ..FnConfig::default() };
+        let system_set = system_set(
+            &parse_quote!(SystemSet),
+            &function.vis,
+            &[&function.attrs[0]],
+        );
+        let system = generate(&FnConfig::default(), function.clone());
+        assert_generates(&config, function, &quote!(#system_set #system));
+    }
+
+    #[test]
+    fn debug_release() {
+        let function: syn::ItemFn = parse_quote!(
+            fn system() {}
+        );
+        let config = FnConfig {
+            debug: Some(parse_quote!(Panic)),
+            release: Some(parse_quote!(Ignore)),
+            ..FnConfig::default()
+        };
+        let with_error = |error_type| {
+            let config = FnConfig { error_type, ..FnConfig::default() };
+            generate(&config, function.clone())
+        };
+        let debug = with_error(parse_quote!(Panic));
+        let release = with_error(parse_quote!(Ignore));
+        let expected = quote! {
+            #[cfg(debug_assertions)]
+            #debug
+            #[cfg(not(debug_assertions))]
+            #release
+        };
+        assert_generates(&config, function, &expected);
+    }
+}
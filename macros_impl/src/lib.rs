@@ -1,7 +1,10 @@
 #![doc = include_str!("../README.md")]
 use proc_macro::TokenStream as TokenStream1;
-use syn::parse_macro_input;
+use syn::{parse::ParseStream, parse_macro_input};
 
+use config::FnConfig;
+
+mod config;
 mod generate;
 
 /// `sysfail` is an attribute macro you can slap on top of your systems to define
@@ -10,23 +13,43 @@ mod generate;
 /// See [`macro@exclusive_sysfail`] for **exclusive systems** handling.
 #[proc_macro_attribute]
 pub fn sysfail(attrs: TokenStream1, input: TokenStream1) -> TokenStream1 {
-    let mut config = generate::FnConfig::new();
+    let mut config = FnConfig::default();
 
-    if !attrs.is_empty() {
-        config.error_type = parse_macro_input!(attrs as syn::Type);
-    }
+    let parser = |input: ParseStream| config.parse_attrs(input);
+    parse_macro_input!(attrs with parser);
     let input = parse_macro_input!(input as syn::ItemFn);
-    generate::sysfail(&config, input).into()
+    generate::generate(&config, input).into()
 }
 /// Similar to [`macro@sysfail`] but allows usage on **exclusive systems**.
 #[proc_macro_attribute]
 pub fn exclusive_sysfail(attrs: TokenStream1, input: TokenStream1) -> TokenStream1 {
-    let mut config = generate::FnConfig::new();
-    config.exclusive = true;
+    let mut config = FnConfig { exclusive: true, ..FnConfig::default() };
 
-    if !attrs.is_empty() {
-        config.error_type = parse_macro_input!(attrs as syn::Type);
-    }
+    let parser = |input: ParseStream| config.parse_attrs(input);
+    parse_macro_input!(attrs with parser);
     let input = parse_macro_input!(input as syn::ItemFn);
-    generate::sysfail(&config, input).into()
+    generate::generate(&config, input).into()
+}
+/// Apply [`macro@sysfail`] to all functions in a module.
+///
+/// Functions that already have a `#[sysfail]` or `#[exclusive_sysfail]`
+/// attribute are left as is. Use `#[sysfail(skip)]` to exclude a function.
+#[proc_macro_attribute]
+pub fn sysfail_mod(attrs: TokenStream1, input: TokenStream1) -> TokenStream1 {
+    let mut config = FnConfig::default();
+
+    let parser = |input: ParseStream| config.parse_attrs(input);
+    parse_macro_input!(attrs with parser);
+    let input = parse_macro_input!(input as syn::ItemMod);
+    generate::sysfail_mod(&config, input).into()
+}
+/// Handle errors in a block inside a regular system, see [`macro@sysfail`].
+///
+/// Usage: `sysfail_block!(param, FailureType, { body })`. `param` is an
+/// expression evaluating to the `Failure::Param` of `FailureType`, it is only
+/// evaluated if `body` returns an error.
+#[proc_macro]
+pub fn sysfail_block(input: TokenStream1) -> TokenStream1 {
+    let config = parse_macro_input!(input as generate::BlockConfig);
+    generate::sysfail_block(&config).into()
 }
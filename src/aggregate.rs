@@ -0,0 +1,129 @@
+use std::fmt::Write;
+
+use bevy_ecs::system::{lifetimeless::SResMut, ResMut, Resource, SystemParam};
+use bevy_ecs::world::World;
+use bevy_utils::{tracing::warn, HashMap};
+
+use crate::{Callsite, Failure, Level, WarnMissing};
+
+/// The errors pushed by [`Aggregate`] since the last [`report_aggregated`].
+///
+/// Errors are counted per callsite target, which is the system's path,
+/// unless the `target` option is used.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct SysfailAggregator {
+    counts: HashMap<&'static str, usize>,
+}
+impl SysfailAggregator {
+    /// How many errors were pushed with the given `target`.
+    #[must_use]
+    pub fn count(&self, target: &str) -> usize {
+        self.counts.get(target).copied().unwrap_or(0)
+    }
+    /// The targets with errors and their counts, sorted by target.
+    #[must_use]
+    pub fn counts(&self) -> Vec<(&'static str, usize)> {
+        let mut counts: Vec<_> = self.counts.iter().map(|(t, c)| (*t, *c)).collect();
+        counts.sort_unstable();
+        counts
+    }
+    /// Whether no errors were pushed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+/// Log a summary of the errors in [`SysfailAggregator`] and clear it.
+///
+/// Add it after the systems using [`Aggregate`], for example
+/// `report_aggregated.after(NetworkSet)`. Nothing is logged if there were no
+/// errors. Since this clears the aggregator, errors are aggregated over a
+/// single frame if it runs each frame.
+///
+/// The summary is a warning, such as
+/// `"2 systems failed this frame: net::connect (3 errors), net::sync (1 errors)"`.
+pub fn report_aggregated(mut aggregator: ResMut<SysfailAggregator>) {
+    if aggregator.is_empty() {
+        return;
+    }
+    let counts = aggregator.counts();
+    let mut summary = format!("{} systems failed this frame: ", counts.len());
+    for (i, (target, count)) in counts.iter().enumerate() {
+        let sep = if i == 0 { "" } else { ", " };
+        let _ = write!(summary, "{sep}{target} ({count} errors)");
+    }
+    warn!("{summary}");
+    aggregator.counts.clear();
+}
+
+/// Count the error in the [`SysfailAggregator`] resource, to report it with
+/// the other errors of the frame in [`report_aggregated`].
+///
+/// The error itself is discarded, use [`Tee`](crate::prelude::Tee) to also
+/// log it. If `SysfailAggregator` doesn't exist, the error is not counted,
+/// and a warning is logged the first time it happens in a given system.
+/// Use `SysfailPlugin::default().with_failure::<Aggregate<T>>()` to insert it.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct NetworkSet;
+///
+/// #[sysfail(Aggregate<&'static str>, target = "connect")]
+/// fn connect() {
+///     let () = Err("Connection refused")?;
+/// }
+/// #[sysfail(Aggregate<&'static str>, target = "sync")]
+/// fn sync() {
+///     let () = Err("Out of sync")?;
+/// }
+/// fn check(aggregator: Res<SysfailAggregator>) {
+///     assert_eq!(aggregator.counts(), [("connect", 1), ("sync", 1)]);
+/// }
+/// let mut world = World::new();
+/// world.init_resource::<SysfailAggregator>();
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems((
+///     (connect, sync).in_set(NetworkSet),
+///     (check, report_aggregated).chain().after(NetworkSet),
+/// ));
+/// // Logs "2 systems failed this frame: connect (1 errors), sync (1 errors)"
+/// schedule.run(&mut world);
+/// assert!(world.resource::<SysfailAggregator>().is_empty());
+/// ```
+pub struct Aggregate<T>(pub T);
+
+impl<U: From<T>, T: std::fmt::Debug> From<T> for Aggregate<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+impl<T> Failure for Aggregate<T> {
+    type Param = (Option<SResMut<SysfailAggregator>>, WarnMissing<'static>);
+
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(
+        self,
+        (aggregator, mut missing): <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        let Some(mut aggregator) = aggregator else {
+            missing.warn::<SysfailAggregator>("Aggregate");
+            return;
+        };
+        let target = callsite.map_or("unknown", |callsite| callsite.metadata().target());
+        *aggregator.counts.entry(target).or_default() += 1;
+    }
+
+    fn init(world: &mut World) {
+        world.init_resource::<SysfailAggregator>();
+    }
+}
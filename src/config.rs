@@ -0,0 +1,145 @@
+use bevy_ecs::system::Resource;
+use bevy_utils::{tracing::level_filters::LevelFilter, HashMap};
+
+/// Runtime configuration of [`Log`](crate::prelude::Log) systems.
+///
+/// This resource is optional. When it is absent, `Log` behaves as if the
+/// default `SysfailConfig` was inserted. Changes to the resource take effect
+/// the next time an error is handled.
+///
+/// # Example
+///
+/// Disable deduplication to see every error while debugging:
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy::ecs::schedule::ExecutorKind;
+/// use bevy_mod_sysfail::prelude::*;
+/// use bevy_mod_sysfail::{testing::capture_logs, SysfailConfig};
+///
+/// #[sysfail(Log<&'static str>)]
+/// fn failable_system() {
+///     let () = Err("Always shown")?;
+/// }
+/// let mut world = World::new();
+/// world.init_resource::<Time>();
+/// world.insert_resource(SysfailConfig { dedup_enabled: false });
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems(failable_system);
+///
+/// let logs = capture_logs(|| {
+///     for _ in 0..3 {
+///         schedule.run(&mut world);
+///     }
+/// });
+/// assert_eq!(logs.len(), 3);
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct SysfailConfig {
+    /// Whether to skip errors logged within their
+    /// [`Dedup::cooldown`](crate::Dedup::cooldown).
+    ///
+    /// When `false`, every error is logged. This doesn't affect the log
+    /// level filter. `true` by default.
+    pub dedup_enabled: bool,
+}
+impl Default for SysfailConfig {
+    fn default() -> Self {
+        Self { dedup_enabled: true }
+    }
+}
+
+/// Per-system overrides of the log level filter of [`Log`](crate::prelude::Log)
+/// systems.
+///
+/// This resource is optional. When it is inserted, and the tracing target of
+/// a system is listed, `Log` compares the level of the error with the listed
+/// [`LevelFilter`] instead of the current global filter. The target of a
+/// system is its path, such as `my_crate::my_system`, unless set with
+/// the `target` option of `#[sysfail]`.
+///
+/// Overrides only apply to `Log` and its deduplicating variants, such as
+/// [`LogVerbose`] or [`LogShared`]. The other logging `Failure`s, such as
+/// [`LogSimply`], always use the current global filter.
+///
+/// [`LogVerbose`]: crate::prelude::LogVerbose
+/// [`LogShared`]: crate::prelude::LogShared
+/// [`LogSimply`]: crate::prelude::LogSimply
+///
+/// The filters of the tracing subscriber, such as the `EnvFilter` of bevy's
+/// `LogPlugin`, are not checked for listed systems, so an override can also
+/// enable errors the subscriber would filter out. Levels disabled at compile
+/// time, with [`STATIC_MAX_LEVEL`], are never logged.
+///
+/// [`STATIC_MAX_LEVEL`]: bevy_utils::tracing::level_filters::STATIC_MAX_LEVEL
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+/// use bevy_mod_sysfail::{testing::run_and_capture, LevelFilter, SysfailLevelOverrides};
+///
+/// #[sysfail(Log<&'static str>, target = "noisy")]
+/// fn noisy_system() {
+///     let () = Err("Quieted")?;
+/// }
+/// let mut world = World::new();
+/// world.insert_resource(SysfailLevelOverrides::default().with("noisy", LevelFilter::ERROR));
+/// assert!(run_and_capture(&mut world, noisy_system).is_empty());
+/// ```
+///
+/// Enabling the `DEBUG` errors of a system, while the subscriber only logs
+/// `WARN` and more severe events:
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use bevy::log::tracing_subscriber::{layer::{Context, SubscriberExt}, Layer, Registry};
+/// use bevy::{ecs::system::RunSystemOnce, prelude::*};
+/// use bevy_mod_sysfail::{prelude::*, LevelFilter, SysfailLevelOverrides};
+///
+/// static LOGGED: AtomicUsize = AtomicUsize::new(0);
+///
+/// struct CountEvents;
+/// impl<S: tracing::Subscriber> Layer<S> for CountEvents {
+///     fn on_event(&self, _: &tracing::Event, _: Context<S>) {
+///         LOGGED.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// #[sysfail(Log<&'static str, Debug>, target = "verbose")]
+/// fn verbose_system() {
+///     let () = Err("Logged despite the subscriber filter")?;
+/// }
+/// #[sysfail(Log<&'static str, Debug>)]
+/// fn other_system() {
+///     let () = Err("Filtered out by the subscriber")?;
+/// }
+/// let mut world = World::new();
+/// world.insert_resource(SysfailLevelOverrides::default().with("verbose", LevelFilter::DEBUG));
+/// let subscriber = Registry::default().with(LevelFilter::WARN).with(CountEvents);
+/// tracing::subscriber::with_default(subscriber, || {
+///     world.run_system_once(verbose_system);
+///     world.run_system_once(other_system);
+/// });
+/// assert_eq!(LOGGED.load(Ordering::Relaxed), 1);
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SysfailLevelOverrides {
+    /// The level filter of each system, keyed by its target.
+    pub filters: HashMap<String, LevelFilter>,
+}
+impl SysfailLevelOverrides {
+    /// Use `filter` for the systems with the `target` tracing target.
+    #[must_use]
+    pub fn with(mut self, target: impl Into<String>, filter: LevelFilter) -> Self {
+        self.filters.insert(target.into(), filter);
+        self
+    }
+    /// The level filter of the `target` system, if overridden.
+    #[must_use]
+    pub fn get(&self, target: &str) -> Option<LevelFilter> {
+        self.filters.get(target).copied()
+    }
+}
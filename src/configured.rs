@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::{__macro::Configure, Callsite, Failure, Level};
+
+/// A value handling the errors of [`Configured`] systems.
+///
+/// Unlike [`Failure`], this is implemented by values, referenced with the
+/// `mode_ref = PATH` option of `#[sysfail]`, where `PATH` is a `static` or
+/// a `const`. Since `handle_error` takes `&self`, the mode can read its
+/// configuration at runtime, for example from atomics.
+pub trait FailureMode<T>: Sync + 'static {
+    /// Handle `error`, with the callsite of the system, if it has one.
+    fn handle_error(&self, error: T, callsite: Option<&'static dyn Callsite>);
+}
+
+/// Handle errors with the [`FailureMode`] of the `mode_ref` option.
+///
+/// `#[sysfail(mode_ref = MY_MODE)]` is the same as
+/// `#[sysfail(Configured<Box<dyn Error>>, mode_ref = MY_MODE)]`. `Configured`
+/// can't be used without `mode_ref`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::error::Error;
+///
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, Callsite, FailureMode};
+///
+/// struct CountErrors(AtomicUsize);
+///
+/// impl FailureMode<Box<dyn Error>> for CountErrors {
+///     fn handle_error(&self, _: Box<dyn Error>, _: Option<&'static dyn Callsite>) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+/// static MY_MODE: CountErrors = CountErrors(AtomicUsize::new(0));
+///
+/// #[sysfail(mode_ref = MY_MODE)]
+/// fn failable_system() {
+///     let () = Err("Counted by MY_MODE")?;
+/// }
+/// let mut app = App::new();
+/// app.add_systems(Update, failable_system);
+/// app.update();
+/// app.update();
+/// assert_eq!(MY_MODE.0.load(Ordering::Relaxed), 2);
+/// ```
+pub struct Configured<T: 'static>(pub T, Option<&'static dyn FailureMode<T>>);
+
+impl<U: From<T> + 'static, T: fmt::Debug> From<T> for Configured<U> {
+    fn from(t: T) -> Self {
+        Self(t.into(), None)
+    }
+}
+
+impl<T: 'static> Configure for Configured<T> {
+    type Error = T;
+
+    fn set_mode(&mut self, mode: &'static dyn FailureMode<T>) {
+        self.1 = Some(mode);
+    }
+}
+
+impl<T: 'static> Failure for Configured<T> {
+    type Param = ();
+
+    const LEVEL: Level = Level::WARN;
+
+    fn handle_error(self, (): (), callsite: Option<&'static impl Callsite>) {
+        let Some(mode) = self.1 else {
+            panic!("`Configured` needs the `mode_ref` option of `#[sysfail]`");
+        };
+        mode.handle_error(self.0, callsite.map(|c| c as &dyn Callsite));
+    }
+}
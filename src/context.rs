@@ -0,0 +1,102 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::{self, Write};
+use std::mem;
+
+thread_local! {
+    /// The context of the error being handled on this thread.
+    static CONTEXT: RefCell<Vec<(&'static str, String)>> = const { RefCell::new(Vec::new()) };
+    /// Whether the system handling an error on this thread is compiled with
+    /// `cfg(test)`.
+    static CFG_TEST: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The values recorded with `sysfail_context!` in a system body.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Context(Vec<(&'static str, String)>);
+
+impl Context {
+    /// Set the value of `key` to `value`, replacing the previous one.
+    pub fn record(&mut self, key: &'static str, value: &dyn fmt::Display) {
+        let index = self
+            .0
+            .iter()
+            .position(|(k, _)| *k == key)
+            .unwrap_or_else(|| {
+                self.0.push((key, String::new()));
+                self.0.len() - 1
+            });
+        let buffer = &mut self.0[index].1;
+        buffer.clear();
+        let _ = write!(buffer, "{value}");
+    }
+
+    /// Run `f` with this context, so that the logging `Failure`s include it.
+    ///
+    /// The previous context is restored afterward, even if `f` panics.
+    pub fn scope(self, f: impl FnOnce()) {
+        struct Restore(Vec<(&'static str, String)>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CONTEXT.set(mem::take(&mut self.0));
+            }
+        }
+        let _restore = Restore(CONTEXT.replace(self.0));
+        f();
+    }
+}
+
+/// `(key, value)` pairs of a context, formatted as
+/// `" (key = value, key2 = value2)"`, or an empty string if there are none.
+pub(crate) struct ContextText<I>(pub(crate) I);
+
+impl<'a, I> fmt::Display for ContextText<I>
+where
+    I: Iterator<Item = &'a (&'static str, String)> + Clone,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut fields = self.0.clone();
+        let Some((first_key, first_value)) = fields.next() else {
+            return Ok(());
+        };
+        write!(f, " ({first_key} = {first_value}")?;
+        for (key, value) in fields {
+            write!(f, ", {key} = {value}")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Run `f` with the `(key, value)` pairs of the context of the error being
+/// handled, in the order they were first recorded.
+pub(crate) fn with_context_fields<R>(f: impl FnOnce(&[(&'static str, String)]) -> R) -> R {
+    CONTEXT.with_borrow(|context| f(context))
+}
+
+/// The `cfg!(test)` of the crate defining the `sysfail` system handling an
+/// error, set by the generated code until this is dropped.
+///
+/// `cfg!(test)` is only `true` in the crate under test, so it is evaluated in
+/// the system, not in `bevy_mod_sysfail`.
+#[doc(hidden)]
+pub struct CfgTest(bool);
+
+impl CfgTest {
+    /// Record `cfg_test` for the error handled on this thread.
+    #[must_use]
+    pub fn enter(cfg_test: bool) -> Self {
+        Self(CFG_TEST.replace(cfg_test))
+    }
+}
+impl Drop for CfgTest {
+    fn drop(&mut self) {
+        CFG_TEST.set(self.0);
+    }
+}
+
+/// Whether the system handling an error on this thread is compiled with
+/// `cfg(test)`, see [`CfgTest`].
+#[cfg(feature = "full")]
+pub(crate) fn cfg_test() -> bool {
+    CFG_TEST.get()
+}
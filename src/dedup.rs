@@ -1,4 +1,8 @@
-use std::{fmt, hash::Hash};
+use std::{
+    borrow::Cow,
+    fmt,
+    hash::{Hash, Hasher},
+};
 
 use bevy_utils::Duration;
 
@@ -6,6 +10,10 @@ use bevy_utils::Duration;
 ///
 /// This is used by [`Log`](crate::prelude::Log) to avoid repetitively logging
 /// the same error. This avoids spamming errors in the console.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesn't implement `Dedup`, which `Log` needs to deduplicate errors",
+    note = "implement `Dedup` for `{Self}`, or use `LogSimply`, which doesn't deduplicate errors"
+)]
 pub trait Dedup: fmt::Display {
     /// Used to de-duplicate identical messages to avoid spamming the log.
     type ID: Hash + Eq + Send + Sync + 'static;
@@ -16,12 +24,164 @@ pub trait Dedup: fmt::Display {
     ///
     /// If it returns `Duration::ZERO`, `Log` will print the error each time
     /// it is emitted, acting like [`LogSimply`](crate::prelude::LogSimply).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::fmt;
+    /// use bevy::{ecs::schedule::ExecutorKind, prelude::*, utils::Duration};
+    /// use bevy_mod_sysfail::{prelude::*, testing::capture_logs, Dedup, Level};
+    ///
+    /// #[derive(Debug)]
+    /// struct Flaky;
+    /// impl fmt::Display for Flaky {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "Flaky")
+    ///     }
+    /// }
+    /// impl Dedup for Flaky {
+    ///     type ID = ();
+    ///     fn cooldown(&self) -> Duration {
+    ///         Duration::ZERO
+    ///     }
+    ///     fn identify(&self) {}
+    /// }
+    ///
+    /// #[sysfail(Log<Flaky>)]
+    /// fn every_time() {
+    ///     let () = Err(Flaky)?;
+    /// }
+    /// #[sysfail(Log<&'static str>)]
+    /// fn once_per_second() {
+    ///     let () = Err("Default cooldown")?;
+    /// }
+    /// let mut world = World::new();
+    /// world.init_resource::<Time>();
+    /// let mut schedule = Schedule::default();
+    /// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    /// schedule.add_systems((every_time, once_per_second).chain());
+    /// let logs = capture_logs(|| (0..3).for_each(|_| schedule.run(&mut world)));
+    /// let warnings: Vec<_> = logs.iter().filter(|(level, _)| *level == Level::WARN).collect();
+    /// let count = |message: &str| warnings.iter().filter(|(_, m)| m == message).count();
+    /// assert_eq!(count("Flaky"), 3);
+    /// assert_eq!(count("Default cooldown"), 1);
+    /// ```
+    ///
+    /// # Per-value cooldowns
+    ///
+    /// `Log` calls `cooldown` on each error, so different values of the same
+    /// type can have different cooldowns. Errors are throttled independently
+    /// for each [`Self::identify`] ID. When errors with the same ID have
+    /// different cooldowns, the cooldown of the error being handled wins: it
+    /// is compared with the time since the previous error with this ID,
+    /// whatever the cooldown of the previous error was.
+    /// ```rust
+    /// use std::fmt;
+    /// use bevy::{ecs::schedule::ExecutorKind, prelude::*, utils::Duration};
+    /// use bevy_mod_sysfail::{prelude::*, testing::capture_logs, Dedup};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    /// enum NetError {
+    ///     Timeout,
+    ///     Glitch,
+    /// }
+    /// impl fmt::Display for NetError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "{self:?}")
+    ///     }
+    /// }
+    /// impl Dedup for NetError {
+    ///     type ID = Self;
+    ///     fn cooldown(&self) -> Duration {
+    ///         match self {
+    ///             NetError::Timeout => Duration::from_secs(10),
+    ///             NetError::Glitch => Duration::from_secs(1),
+    ///         }
+    ///     }
+    ///     fn identify(&self) -> Self {
+    ///         *self
+    ///     }
+    /// }
+    ///
+    /// #[sysfail(Log<NetError>)]
+    /// fn connect() {
+    ///     let () = Err(NetError::Timeout)?;
+    /// }
+    /// #[sysfail(Log<NetError>)]
+    /// fn sync() {
+    ///     let () = Err(NetError::Glitch)?;
+    /// }
+    /// let mut world = World::new();
+    /// world.init_resource::<Time>();
+    /// let mut schedule = Schedule::default();
+    /// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    /// schedule.add_systems((connect, sync).chain());
+    /// let mut messages_at = |secs| {
+    ///     let elapsed = world.resource::<Time>().elapsed();
+    ///     world.resource_mut::<Time>().advance_by(Duration::from_secs(secs) - elapsed);
+    ///     let logs = capture_logs(|| schedule.run(&mut world));
+    ///     logs.into_iter().map(|(_, message)| message).collect::<Vec<_>>()
+    /// };
+    /// assert_eq!(messages_at(0), ["Timeout", "Glitch"]);
+    /// assert_eq!(messages_at(2), ["Glitch"]);
+    /// // 10 seconds since the previous `Timeout`, at 2 seconds
+    /// assert_eq!(messages_at(12), ["Timeout", "Glitch"]);
+    /// ```
     fn cooldown(&self) -> Duration {
         Duration::from_secs(1)
     }
 
     /// What constitutes "distinct" error types.
     fn identify(&self) -> Self::ID;
+
+    /// Hash the [`Self::identify`] ID into `hasher`.
+    ///
+    /// [`Log`](crate::prelude::Log) only stores the hash of the ID, so
+    /// override this to avoid building an owned `ID` on each error, for example
+    /// by hashing a borrowed `&str` rather than allocating a `String`.
+    /// It must hash the same way as `Self::ID` would.
+    ///
+    /// Note that since only a 64 bits hash is stored, two distinct IDs may
+    /// collide, and share the same cooldown. This is extremely unlikely,
+    /// and harmless: at worst, an error is not logged when it should be.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::{fmt, hash::{Hash, Hasher}};
+    /// use bevy_mod_sysfail::Dedup;
+    ///
+    /// struct AssetError { path: String }
+    /// impl fmt::Display for AssetError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "Failed to load {}", self.path)
+    ///     }
+    /// }
+    /// impl Dedup for AssetError {
+    ///     type ID = String;
+    ///     fn identify(&self) -> String {
+    ///         self.path.clone()
+    ///     }
+    ///     // `str` hashes the same way as `String`, without cloning
+    ///     fn id_hash(&self, hasher: &mut impl Hasher) {
+    ///         self.path.as_str().hash(hasher);
+    ///     }
+    /// }
+    /// ```
+    fn id_hash(&self, hasher: &mut impl Hasher) {
+        self.identify().hash(hasher);
+    }
+
+    /// The detailed message used by [`LogVerbose`](crate::prelude::LogVerbose).
+    ///
+    /// By default, this is the same as `Display`.
+    ///
+    /// # Errors
+    ///
+    /// When writing to `f` fails.
+    fn fmt_verbose(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
 }
 
 impl Dedup for &'static str {
@@ -30,13 +190,87 @@ impl Dedup for &'static str {
         self
     }
 }
+/// Errors are deduplicated by their full message.
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail(Log<String, Error>)]
+/// fn failable_system(query: Query<&Transform>) {
+///     let () = Err(format!("Found {} transforms", query.iter().len()))?;
+/// }
+/// ```
+impl Dedup for String {
+    type ID = Self;
+    fn identify(&self) -> Self {
+        self.clone()
+    }
+    fn id_hash(&self, hasher: &mut impl Hasher) {
+        self.as_str().hash(hasher);
+    }
+}
+/// Errors are deduplicated by their full message.
+impl Dedup for Cow<'static, str> {
+    type ID = Self;
+    fn identify(&self) -> Self {
+        self.clone()
+    }
+    fn id_hash(&self, hasher: &mut impl Hasher) {
+        (**self).hash(hasher);
+    }
+}
 impl Dedup for Box<dyn std::error::Error> {
     type ID = ();
     /// By default, only print a single error per system.
     fn identify(&self) {}
+    /// Print the error and its chain of [`source`](std::error::Error::source)s.
+    fn fmt_verbose(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self}")?;
+        let mut source = self.source();
+        while let Some(error) = source {
+            write!(f, "\ncaused by: {error}")?;
+            source = error.source();
+        }
+        Ok(())
+    }
 }
 impl Dedup for anyhow::Error {
     type ID = ();
     /// By default, only print a single error per system.
     fn identify(&self) {}
+    /// Print the error with `{:?}`, this includes the chain of causes
+    /// and the backtrace, if enabled.
+    fn fmt_verbose(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+#[cfg(feature = "eyre")]
+impl Dedup for eyre::Report {
+    type ID = ();
+    /// By default, only print a single error per system.
+    fn identify(&self) {}
+    /// Print the report with `{:?}`, this includes the chain of causes
+    /// and the sections added by the installed `EyreHandler`, if any.
+    fn fmt_verbose(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl Dedup for crate::NoneError {
+    type ID = (&'static str, u32, u32);
+    /// Each `?` has its own cooldown.
+    fn identify(&self) -> Self::ID {
+        let location = self.location;
+        (location.file(), location.line(), location.column())
+    }
+}
+impl Dedup for crate::UnitError {
+    type ID = ();
+    /// `()` errors are all the same.
+    fn identify(&self) {}
+}
+impl Dedup for crate::CaughtPanic {
+    type ID = ();
+    /// By default, only print a single panic per system.
+    fn identify(&self) {}
 }
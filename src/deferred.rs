@@ -0,0 +1,162 @@
+use std::any::{type_name, Any};
+
+use bevy_ecs::system::{lifetimeless::SResMut, Local, Resource, SystemParam, SystemState};
+use bevy_ecs::world::World;
+use bevy_utils::tracing::{callsite::DefaultCallsite, Metadata};
+
+use crate::{log_routed::LeveledCallsites, Callsite, Failure, Level, WarnMissing};
+
+/// The callsite of a deferred error, passed to `F` by [`flush_deferred<F>`].
+///
+/// The callsites of `sysfail` systems are either a `DefaultCallsite` or
+/// `LeveledCallsites`, they are kept with their type, so that `F` gets the
+/// same callsite as if the error wasn't deferred. Other callsites are dropped.
+#[derive(Clone, Copy)]
+enum DeferredCallsite {
+    Default(&'static DefaultCallsite),
+    Leveled(&'static LeveledCallsites),
+}
+impl DeferredCallsite {
+    fn new(callsite: &'static impl Callsite) -> Option<Self> {
+        let callsite: &'static dyn Any = callsite;
+        let default = callsite.downcast_ref().map(Self::Default);
+        default.or_else(|| callsite.downcast_ref().map(Self::Leveled))
+    }
+    fn metadata(self) -> &'static Metadata<'static> {
+        match self {
+            Self::Default(callsite) => callsite.metadata(),
+            Self::Leveled(callsite) => callsite.metadata(),
+        }
+    }
+}
+
+/// The errors of [`Defer<F>`] systems, waiting for [`flush_deferred<F>`].
+#[derive(Resource)]
+pub struct DeferredErrors<F>(Vec<(F, Option<DeferredCallsite>)>);
+impl<F> Default for DeferredErrors<F> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+impl<F> DeferredErrors<F> {
+    /// How many errors are waiting to be handled.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    /// Whether no errors are waiting to be handled.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Handle the errors in [`DeferredErrors<F>`] with `F`, in the order they
+/// were returned, and clear it.
+///
+/// The commands of `F::Param` are applied after each error.
+/// `SysfailPlugin::default().with_deferred::<F>()` adds this system to the
+/// `Last` schedule, so that it runs every frame, after `Update`.
+pub fn flush_deferred<F: Failure + Send + Sync + 'static>(
+    world: &mut World,
+    mut state: Local<Option<SystemState<F::Param>>>,
+) {
+    let Some(mut deferred) = world.get_resource_mut::<DeferredErrors<F>>() else {
+        return;
+    };
+    let errors = std::mem::take(&mut deferred.0);
+    if errors.is_empty() {
+        return;
+    }
+    let state = state.get_or_insert_with(|| SystemState::new(world));
+    for (failure, callsite) in errors {
+        let param = state.get_mut(world);
+        let result = match callsite {
+            Some(DeferredCallsite::Default(callsite)) => {
+                failure.handle_error_fallible(param, Some(callsite))
+            }
+            Some(DeferredCallsite::Leveled(callsite)) => {
+                failure.handle_error_fallible(param, Some(callsite))
+            }
+            None => failure.handle_error_fallible(param, None::<&'static DefaultCallsite>),
+        };
+        state.apply(world);
+        if let Err(error) = result {
+            let system = callsite.and_then(|callsite| callsite.metadata().module_path());
+            let system = system.unwrap_or_else(type_name::<Defer<F>>);
+            crate::__macro::handler_failed(system, &*error);
+        }
+    }
+}
+
+/// Handle errors with `F` at the end of the frame, rather than when the
+/// system returns.
+///
+/// Errors are pushed to the [`DeferredErrors<F>`] resource, with their
+/// callsite, and handled later by the [`flush_deferred<F>`] system. Setup
+/// both with `SysfailPlugin::default().with_deferred::<F>()`. This avoids the
+/// side effects of `F` in the middle of the frame, and `F::Param` doesn't
+/// conflict with the parameters of the system.
+///
+/// If `DeferredErrors<F>` doesn't exist, the error is discarded, and a
+/// warning is logged the first time it happens in a given system.
+/// [`Failure::on_success`] of `F` is not called.
+///
+/// This is not named `Deferred`, to not collide with the `Deferred` system
+/// parameter of the bevy prelude.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+/// use bevy_mod_sysfail::{prelude::*, testing::capture_logs, Level};
+///
+/// #[sysfail(Defer<LogSimply<&'static str, Error>>)]
+/// fn failable_system() {
+///     let () = Err("Handled at the end of the frame")?;
+/// }
+/// let mut world = World::new();
+/// world.init_resource::<DeferredErrors<LogSimply<&'static str, Error>>>();
+/// let mut update = Schedule::default();
+/// update.set_executor_kind(ExecutorKind::SingleThreaded);
+/// update.add_systems(failable_system);
+/// let mut last = Schedule::default();
+/// last.set_executor_kind(ExecutorKind::SingleThreaded);
+/// last.add_systems(flush_deferred::<LogSimply<&'static str, Error>>);
+///
+/// assert!(capture_logs(|| update.run(&mut world)).is_empty());
+/// let logs = capture_logs(|| last.run(&mut world));
+/// assert_eq!(logs, [(Level::ERROR, "Handled at the end of the frame".to_owned())]);
+/// ```
+pub struct Defer<F>(pub F);
+
+impl<T: std::fmt::Debug, F: From<T>> From<T> for Defer<F> {
+    fn from(t: T) -> Self {
+        Self(F::from(t))
+    }
+}
+
+impl<F: Failure + Send + Sync + 'static> Failure for Defer<F> {
+    type Param = (Option<SResMut<DeferredErrors<F>>>, WarnMissing<'static>);
+
+    const LEVEL: Level = F::LEVEL;
+
+    fn handle_error(
+        self,
+        (deferred, mut missing): <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        let Some(mut deferred) = deferred else {
+            missing.warn::<DeferredErrors<F>>("Defer");
+            return;
+        };
+        deferred
+            .0
+            .push((self.0, callsite.and_then(DeferredCallsite::new)));
+    }
+
+    fn init(world: &mut World) {
+        world.init_resource::<DeferredErrors<F>>();
+        F::init(world);
+    }
+}
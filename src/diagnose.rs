@@ -0,0 +1,117 @@
+use std::{fmt, marker::PhantomData};
+
+use bevy::core::FrameCount;
+use bevy::diagnostic::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+use bevy_ecs::system::{lifetimeless::SRes, lifetimeless::SResMut, Local, SystemParam};
+use bevy_ecs::world::World;
+use bevy_utils::Instant;
+
+use crate::{Callsite, Failure, Level};
+
+/// The [`DiagnosticPath`] used by [`DiagnoseErrors`].
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::diagnostic::DiagnosticPath;
+/// use bevy_mod_sysfail::ErrorDiagnostic;
+///
+/// struct NetworkErrors;
+/// impl ErrorDiagnostic for NetworkErrors {
+///     const PATH: DiagnosticPath = DiagnosticPath::const_new("network/errors");
+/// }
+/// ```
+pub trait ErrorDiagnostic: Send + Sync + 'static {
+    /// The path of the diagnostic measuring the errors.
+    const PATH: DiagnosticPath;
+}
+
+/// Record how many errors the system returned this frame in the `D::PATH`
+/// [`Diagnostic`].
+///
+/// A measurement is added to the diagnostic on each error, its value is the
+/// number of errors returned by this system in the current frame. Frames
+/// without errors do not add measurements.
+///
+/// The diagnostic must be registered, either with
+/// `app.register_diagnostic(Diagnostic::new(D::PATH))`, or with
+/// `SysfailPlugin::default().with_failure::<DiagnoseErrors<T, D>>()`.
+/// Nothing is recorded if the `DiagnosticsStore` resource or the diagnostic
+/// doesn't exist.
+///
+/// This doesn't log the error, use [`Tee`](crate::prelude::Tee) to also
+/// log it.
+///
+/// This requires the `bevy_diagnostic` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::diagnostic::{DiagnosticPath, DiagnosticsStore};
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, ErrorDiagnostic};
+///
+/// struct PathErrors;
+/// impl ErrorDiagnostic for PathErrors {
+///     const PATH: DiagnosticPath = DiagnosticPath::const_new("pathfinding/errors");
+/// }
+///
+/// #[sysfail(Tee<Log<&'static str>, DiagnoseErrors<&'static str, PathErrors>>)]
+/// fn pathfinding(query: Query<&Transform>) {
+///     let () = Err("No path found")?;
+/// }
+///
+/// let failure = SysfailPlugin::default()
+///     .with_failure::<DiagnoseErrors<&'static str, PathErrors>>();
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, failure))
+///     .add_systems(Update, pathfinding);
+/// app.update();
+///
+/// let store = app.world.resource::<DiagnosticsStore>();
+/// assert_eq!(store.get(&PathErrors::PATH).unwrap().value(), Some(1.0));
+/// ```
+pub struct DiagnoseErrors<T, D>(pub T, PhantomData<D>);
+
+impl<U: From<T>, T: fmt::Debug, D> From<T> for DiagnoseErrors<U, D> {
+    fn from(t: T) -> Self {
+        Self(t.into(), PhantomData)
+    }
+}
+
+impl<T, D: ErrorDiagnostic> Failure for DiagnoseErrors<T, D> {
+    type Param = (
+        Option<SResMut<DiagnosticsStore>>,
+        Option<SRes<FrameCount>>,
+        Local<'static, (u32, u32)>,
+    );
+
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(
+        self,
+        (store, frame, mut errors): <Self::Param as SystemParam>::Item<'_, '_>,
+        _: Option<&'static impl Callsite>,
+    ) {
+        let frame = frame.map_or(0, |frame| frame.0);
+        let (last_frame, count) = &mut *errors;
+        if *last_frame != frame {
+            *last_frame = frame;
+            *count = 0;
+        }
+        *count += 1;
+        let Some(diagnostic) = store.and_then(|s| s.into_inner().get_mut(&D::PATH)) else {
+            return;
+        };
+        let time = Instant::now();
+        let value = f64::from(*count);
+        diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+    }
+
+    fn init(world: &mut World) {
+        let mut store = world.get_resource_or_insert_with(DiagnosticsStore::default);
+        if store.get(&D::PATH).is_none() {
+            store.add(Diagnostic::new(D::PATH));
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::{array, fmt};
+
+use bevy_utils::tracing::field::{self, DisplayValue, Field, Value};
+use bevy_utils::tracing::level_filters::{LevelFilter, STATIC_MAX_LEVEL};
+use bevy_utils::tracing::{debug, error, info, trace, warn};
+use bevy_utils::tracing::{dispatcher, Event, Metadata};
+
+use crate::context::{with_context_fields, ContextText};
+use crate::Level;
+
+/// The most fields of the events of [`dispatch_message`], including the message.
+const MAX_FIELDS: usize = 32;
+
+/// Whether the current subscriber is interested in events with `meta`.
+///
+/// `Event::dispatch` doesn't check it, so without this, the filters of the
+/// subscriber, such as the `EnvFilter` of bevy's `LogPlugin`, are bypassed.
+pub(crate) fn is_enabled(meta: &Metadata<'_>) -> bool {
+    dispatcher::get_default(|dispatch| dispatch.enabled(meta))
+}
+
+/// Log `message` with the error context, if `level` passes
+/// `STATIC_MAX_LEVEL` and the filters.
+///
+/// Without `override_filter`, the filters are `LevelFilter::current()` and the
+/// subscriber's filters. With it, only `override_filter` is checked, so that
+/// it can enable errors the subscriber would filter out.
+///
+/// The context values are recorded in the fields of `meta` named after their
+/// key, the `sysfail` macro adds them to its callsites. The values without
+/// field are appended to `message`, as `" (key = value, ...)"`.
+///
+/// Without `meta`, the message is logged with [`dispatch_fallback`], with the
+/// whole context appended to it.
+pub(crate) fn dispatch_message(
+    meta: Option<&'static Metadata<'static>>,
+    level: Level,
+    override_filter: Option<LevelFilter>,
+    message: fmt::Arguments,
+) {
+    let enabled = match (override_filter, meta) {
+        (Some(filter), _) => level <= filter,
+        (None, Some(meta)) => level <= LevelFilter::current() && is_enabled(meta),
+        (None, None) => level <= LevelFilter::current(),
+    };
+    if level > STATIC_MAX_LEVEL || !enabled {
+        return;
+    }
+    with_context_fields(|context| {
+        meta.map_or_else(
+            || {
+                let context = ContextText(context.iter());
+                dispatch_fallback(level, format_args!("{message}{context}"));
+            },
+            |meta| dispatch(meta, message, context),
+        );
+    });
+}
+
+/// Dispatch an event with `meta`, recording `message` in its first field,
+/// and the `context` values in the fields of the same name, after the
+/// `sysfail` marker field.
+///
+/// This doesn't check any filter, see [`dispatch_message`].
+pub(crate) fn dispatch(
+    meta: &'static Metadata<'static>,
+    message: fmt::Arguments,
+    context: &[(&'static str, String)],
+) {
+    let fields = meta.fields();
+    let message_field = fields
+        .iter()
+        .next()
+        .expect("FieldSet corrupted (this is a bug)");
+    let context_fields: [Option<(Field, DisplayValue<&str>)>; MAX_FIELDS - 1] =
+        array::from_fn(|i| {
+            let (key, value) = context.get(i)?;
+            let field = fields.iter().skip(2).find(|field| field.name() == *key)?;
+            Some((field, field::display(value.as_str())))
+        });
+    let has_field = |i: usize| context_fields.get(i).is_some_and(Option::is_some);
+    let text = context.iter().enumerate().filter(|(i, _)| !has_field(*i));
+    let record = |message: fmt::Arguments| {
+        let mut values = [(&message_field, None::<&dyn Value>); MAX_FIELDS];
+        values[0].1 = Some(&message);
+        for (value, field) in values[1..].iter_mut().zip(&context_fields) {
+            if let Some((field, display)) = field {
+                *value = (field, Some(display));
+            }
+        }
+        Event::dispatch(meta, &fields.value_set(&values));
+    };
+    let text = ContextText(text.map(|(_, entry)| entry));
+    record(format_args!("{message}{text}"));
+}
+
+/// Log `message` at `level` with the callsites of this function, for the
+/// `Failure`s called without a callsite.
+///
+/// This happens when the `Failure` type depends on the generics of the system,
+/// or when the `Failure` is called outside of a `sysfail` system. The location
+/// and target of the event are then the ones of this function.
+///
+/// Like the callsites of the macro, the name starts with `"sysfail "`, so that
+/// the events are captured by [`testing`](crate::testing).
+pub(crate) fn dispatch_fallback(level: Level, message: fmt::Arguments) {
+    macro_rules! fallback {
+        ($log:ident) => {
+            $log!(name: concat!("sysfail ", file!(), ":", line!()), "{message}")
+        };
+    }
+    match level {
+        Level::TRACE => fallback!(trace),
+        Level::DEBUG => fallback!(debug),
+        Level::INFO => fallback!(info),
+        Level::WARN => fallback!(warn),
+        Level::ERROR => fallback!(error),
+    }
+}
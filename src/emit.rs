@@ -1,8 +1,56 @@
-use bevy_ecs::event::{Event, EventWriter};
+use bevy_ecs::entity::Entity;
+use std::{any::type_name, fmt};
 
-use crate::{Callsite, Failure, Level};
+use bevy_ecs::event::{Event, EventWriter, Events};
+use bevy_ecs::system::{lifetimeless::SResMut, ResMut, Resource, SystemParam};
+use bevy_utils::tracing::Metadata;
+
+use crate::{Callsite, Failure, Level, SysfailHooks};
+
+#[cold]
+fn unregistered_event<E>(failure: &str) -> ! {
+    panic!(
+        "`{failure}<{event}>` is used in a sysfail system, but the `{event}` event is not \
+        registered. Use `app.add_event::<{event}>()` or \
+        `SysfailPlugin::default().with_event::<{event}>()`.",
+        event = type_name::<E>(),
+    )
+}
 
 /// As the `Err` of the return value of a `sysfail` system, send the `E` event.
+///
+/// The `E` event must be registered, with `app.add_event::<E>()` or
+/// `SysfailPlugin::default().with_event::<E>()`, otherwise the system panics
+/// when returning an error, with a message naming the event to register:
+/// ```rust,should_panic
+/// use bevy::{ecs::system::RunSystemOnce, prelude::*};
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[derive(Event)]
+/// struct Hit;
+///
+/// #[sysfail(Emit<Hit>)]
+/// fn hit_system() {
+///     let () = Err(Hit)?;
+/// }
+/// // panics: "`Emit<[…]::Hit>` is used in a sysfail system, but the `[…]::Hit` event
+/// // is not registered. Use `app.add_event::<[…]::Hit>()` or […]"
+/// World::new().run_system_once(hit_system);
+/// ```
+/// This doesn't prevent the system from running, since `Emit` doesn't use an
+/// `EventWriter`, which would panic when initializing the system.
+///
+/// # Event buffering
+///
+/// `Emit` sends the event directly in the `Events<E>` resource with
+/// [`Events::send`], this is exactly what `EventWriter::send` does, so
+/// readers see no difference with an event sent by an `EventWriter`:
+///
+/// - Systems that run after the failing system in the same frame read the
+///   event (use `.after(failing_system)` to guarantee it).
+/// - Systems that run before it read it in the next frame.
+/// - As with all events, the event is dropped after two `Events::update`,
+///   by default, at the start of the frame after the next one.
 pub struct Emit<E>(pub E);
 
 impl<E> From<E> for Emit<E> {
@@ -12,11 +60,420 @@ impl<E> From<E> for Emit<E> {
 }
 
 impl<E: Event + 'static> Failure for Emit<E> {
-    type Param = EventWriter<'static, E>;
+    type Param = (Option<SResMut<Events<E>>>, SysfailHooks<'static>);
+
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(
+        self,
+        (events, hooks): <Self::Param as SystemParam>::Item<'_, '_>,
+        _: Option<&'static impl Callsite>,
+    ) {
+        if !hooks.enabled() {
+            return;
+        }
+        let Some(mut events) = events else {
+            unregistered_event::<E>("Emit")
+        };
+        events.send(self.0);
+    }
+}
+
+/// An error `E` caused by a specific [`Entity`].
+///
+/// This is both the error returned by the system body and the event sent by
+/// [`EmitFor`]. Construct it with [`ForEntity::for_entity`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityError<E> {
+    /// The entity that caused the error.
+    pub entity: Entity,
+    /// The error itself.
+    pub error: E,
+}
+impl<E: Send + Sync + 'static> Event for EntityError<E> {}
+
+/// Attach an [`Entity`] to the `Err` side of a `Result`, see [`EmitFor`].
+pub trait ForEntity<T, E> {
+    /// Wrap the error in an [`EntityError`] for `entity`.
+    ///
+    /// # Errors
+    ///
+    /// When `self` is an `Err`.
+    fn for_entity(self, entity: Entity) -> Result<T, EntityError<E>>;
+}
+impl<T, E> ForEntity<T, E> for Result<T, E> {
+    fn for_entity(self, entity: Entity) -> Result<T, EntityError<E>> {
+        self.map_err(|error| EntityError { entity, error })
+    }
+}
+
+/// Similar to [`Emit`], but sends an [`EntityError<E>`] event, keeping
+/// track of which entity caused the error.
+///
+/// Since `sysfail` can't know what entity failed, the system body must
+/// return an `EntityError`, use [`ForEntity::for_entity`] for this.
+///
+/// Note that you need to register the `EntityError<E>` event with
+/// `app.add_event::<EntityError<E>>()`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail(EmitFor<&'static str>)]
+/// fn check_transforms(query: Query<(Entity, &Transform)>) {
+///     for (entity, transform) in &query {
+///         if transform.scale == Vec3::ZERO {
+///             Err("Zero scale").for_entity(entity)?;
+///         }
+///     }
+/// }
+///
+/// fn read_errors(mut errors: EventReader<EntityError<&'static str>>) {
+///     for EntityError { entity, error } in errors.read() {
+///         println!("{entity:?}: {error}");
+///     }
+/// }
+/// ```
+pub struct EmitFor<E>(pub EntityError<E>);
+
+impl<U, E: From<U>> From<EntityError<U>> for EmitFor<E> {
+    fn from(value: EntityError<U>) -> Self {
+        let EntityError { entity, error } = value;
+        Self(EntityError { entity, error: error.into() })
+    }
+}
+
+impl<E: Send + Sync + 'static> Failure for EmitFor<E> {
+    type Param = (
+        Option<SResMut<Events<EntityError<E>>>>,
+        SysfailHooks<'static>,
+    );
+
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(
+        self,
+        (events, hooks): <Self::Param as SystemParam>::Item<'_, '_>,
+        _: Option<&'static impl Callsite>,
+    ) {
+        if !hooks.enabled() {
+            return;
+        }
+        let Some(mut events) = events else {
+            unregistered_event::<EntityError<E>>("EmitFor")
+        };
+        events.send(self.0);
+    }
+}
+
+/// An error `E` with the location of the system that returned it.
+///
+/// This is the event sent by [`EmitWithSite`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SysfailEvent<E> {
+    /// The error itself.
+    pub inner: E,
+    /// The tracing target of the system, by default its path, such as
+    /// `my_crate::my_system`.
+    pub target: &'static str,
+    /// The file of the system.
+    pub file: &'static str,
+    /// The line of the system.
+    pub line: u32,
+}
+impl<E: Send + Sync + 'static> Event for SysfailEvent<E> {}
+
+/// Similar to [`Emit`], but sends a [`SysfailEvent<E>`] event, with the
+/// system, file and line of the failing system.
+///
+/// The location comes from the callsite metadata of the system, the target is
+/// `"unknown"` when the system has no callsite. Note that you need to register
+/// the `SysfailEvent<E>` event with `app.add_event::<SysfailEvent<E>>()`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::{ecs::system::RunSystemOnce, prelude::*};
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail(EmitWithSite<&'static str>)]
+/// fn failable_system() {
+///     let () = Err("Sent with its location")?;
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<Events<SysfailEvent<&'static str>>>();
+/// world.run_system_once(failable_system);
+/// let events = world.resource::<Events<SysfailEvent<&'static str>>>();
+/// let event = events.iter_current_update_events().next().unwrap();
+/// assert_eq!(event.inner, "Sent with its location");
+/// assert!(event.target.ends_with("failable_system"));
+/// ```
+pub struct EmitWithSite<E>(pub E);
+
+impl<U: From<T>, T: fmt::Debug> From<T> for EmitWithSite<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+impl<E: Send + Sync + 'static> Failure for EmitWithSite<E> {
+    type Param = (
+        Option<SResMut<Events<SysfailEvent<E>>>>,
+        SysfailHooks<'static>,
+    );
+
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(
+        self,
+        (events, hooks): <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        if !hooks.enabled() {
+            return;
+        }
+        let Some(mut events) = events else {
+            unregistered_event::<SysfailEvent<E>>("EmitWithSite")
+        };
+        let meta = callsite.map(|callsite| callsite.metadata());
+        events.send(SysfailEvent {
+            inner: self.0,
+            target: meta.map_or("unknown", Metadata::target),
+            file: meta.and_then(Metadata::file).unwrap_or_default(),
+            line: meta.and_then(Metadata::line).unwrap_or_default(),
+        });
+    }
+}
+
+/// Send an event of one of the `Events` types in a failed system, for
+/// [`EmitMany`].
+///
+/// The event is not sent until [`EventSender::send`] is called, and the
+/// event type only needs to be registered if it is sent.
+pub struct EventSender<'w, E: Event>(Option<ResMut<'w, Events<E>>>);
+
+impl<E: Event> EventSender<'_, E> {
+    /// Send `event`, like [`EventWriter::send`](bevy_ecs::event::EventWriter::send).
+    ///
+    /// # Panics
+    ///
+    /// When the `E` event is not registered.
+    pub fn send(&mut self, event: E) {
+        let Some(events) = &mut self.0 else {
+            unregistered_event::<E>("EmitMany")
+        };
+        events.send(event);
+    }
+}
+
+/// A tuple of up to 4 event types, the [`IntoEvents::Events`] of [`EmitMany`].
+pub trait EventTuple {
+    /// The system param accessing the `Events` of each type.
+    type Param: SystemParam;
+    /// The tuple of [`EventSender`]s passed to [`IntoEvents::write`].
+    type Senders<'w>;
+    /// The senders for the `Param` of the failing system.
+    fn senders<'w>(param: <Self::Param as SystemParam>::Item<'w, '_>) -> Self::Senders<'w>;
+}
+
+macro_rules! impl_event_tuple {
+    ($($event:ident),*) => {
+        impl<$($event: Event),*> EventTuple for ($($event,)*) {
+            type Param = ($(Option<SResMut<Events<$event>>>,)*);
+            type Senders<'w> = ($(EventSender<'w, $event>,)*);
+
+            #[allow(non_snake_case)]
+            fn senders<'w>(($($event,)*): <Self::Param as SystemParam>::Item<'w, '_>) -> Self::Senders<'w> {
+                ($(EventSender($event),)*)
+            }
+        }
+    };
+}
+impl_event_tuple!(A);
+impl_event_tuple!(A, B);
+impl_event_tuple!(A, B, C);
+impl_event_tuple!(A, B, C, D);
+
+/// An error sent as one of several event types by [`EmitMany`].
+pub trait IntoEvents {
+    /// The event types this error can be sent as, such as `(EventA, EventB)`.
+    type Events: EventTuple;
+
+    /// Send `self` with one or several of the `senders`, with one
+    /// [`EventSender`] per event type of `Events`.
+    fn write(self, senders: <Self::Events as EventTuple>::Senders<'_>);
+}
+
+/// Similar to [`Emit`], but the error chooses which events to send, with
+/// [`IntoEvents`]. This is useful when different variants of an error enum
+/// map to different event types.
+///
+/// Only the events sent by [`IntoEvents::write`] need to be registered, with
+/// `app.add_event::<E>()` or `SysfailPlugin::default().with_event::<E>()`.
+/// Sending an unregistered event panics. As with `Emit`, the `Events` of every
+/// event type are accessed mutably, so the system can't also have an
+/// `EventWriter` or `EventReader` for one of them.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::{ecs::system::RunSystemOnce, prelude::*};
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[derive(Event, Debug)]
+/// struct Disconnected(u32);
+/// #[derive(Event, Debug)]
+/// struct ShowPopup(&'static str);
+///
+/// #[derive(Debug)]
+/// enum GameError {
+///     Network(u32),
+///     Ui(&'static str),
+/// }
+/// impl IntoEvents for GameError {
+///     type Events = (Disconnected, ShowPopup);
+///
+///     fn write(self, (mut disconnected, mut popup): (EventSender<Disconnected>, EventSender<ShowPopup>)) {
+///         match self {
+///             GameError::Network(client) => disconnected.send(Disconnected(client)),
+///             GameError::Ui(text) => popup.send(ShowPopup(text)),
+///         }
+///     }
+/// }
+///
+/// #[sysfail(EmitMany<GameError>)]
+/// fn failable_system() {
+///     let () = Err(GameError::Ui("Oops"))?;
+/// }
+///
+/// let mut world = World::new();
+/// // `Disconnected` doesn't need to be registered, since it's not sent
+/// world.init_resource::<Events<ShowPopup>>();
+/// world.run_system_once(failable_system);
+/// assert_eq!(world.resource::<Events<ShowPopup>>().len(), 1);
+/// ```
+pub struct EmitMany<T>(pub T);
+
+impl<U: From<T>, T: fmt::Debug> From<T> for EmitMany<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+impl<T: IntoEvents> Failure for EmitMany<T> {
+    type Param = (<T::Events as EventTuple>::Param, SysfailHooks<'static>);
+
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(
+        self,
+        (param, hooks): <Self::Param as SystemParam>::Item<'_, '_>,
+        _: Option<&'static impl Callsite>,
+    ) {
+        if hooks.enabled() {
+            self.0.write(T::Events::senders(param));
+        }
+    }
+}
+
+/// The events of [`EmitDeduped<E>`] waiting for [`flush_deduped_events`].
+#[derive(Resource)]
+pub struct DedupedEvents<E>(Vec<E>);
+impl<E> Default for DedupedEvents<E> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+/// Send the events collected by [`EmitDeduped<E>`], in the order they were
+/// first emitted.
+///
+/// `SysfailPlugin::default().with_deduped_event::<E>()` adds this system to the
+/// `Last` schedule, so that it runs every frame, after all `EmitDeduped<E>`
+/// systems.
+pub fn flush_deduped_events<E: Event>(
+    mut pending: ResMut<DedupedEvents<E>>,
+    mut events: EventWriter<E>,
+) {
+    events.send_batch(pending.0.drain(..));
+}
+
+/// Similar to [`Emit`], but identical events emitted in the same frame are
+/// sent only once.
+///
+/// Events are collected in the [`DedupedEvents<E>`] resource, skipping those
+/// equal to an event already collected, with [`PartialEq`].
+/// The [`flush_deduped_events`] system then sends them at the end of the
+/// frame, in the order they were first emitted. Setup both with
+/// `SysfailPlugin::default().with_deduped_event::<E>()`, otherwise the
+/// system panics when returning an error.
+///
+/// Since the events are sent at the end of the frame, they are read by the
+/// event readers of the next frame.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[derive(Event, PartialEq, Debug)]
+/// struct MissingAsset(&'static str);
+///
+/// #[sysfail(EmitDeduped<MissingAsset>)]
+/// fn spawn_trees() {
+///     let () = Err(MissingAsset("tree.png"))?;
+/// }
+/// #[sysfail(EmitDeduped<MissingAsset>)]
+/// fn spawn_rocks() {
+///     let () = Err(MissingAsset("rock.png"))?;
+/// }
+/// #[sysfail(EmitDeduped<MissingAsset>)]
+/// fn spawn_forest() {
+///     let () = Err(MissingAsset("tree.png"))?;
+/// }
+/// let mut app = App::new();
+/// app.add_plugins(SysfailPlugin::default().with_deduped_event::<MissingAsset>())
+///     .add_systems(Update, (spawn_trees, spawn_rocks, spawn_forest).chain());
+/// app.update();
+/// let events = app.world.resource::<Events<MissingAsset>>();
+/// let sent: Vec<_> = events.iter_current_update_events().collect();
+/// assert_eq!(sent, [&MissingAsset("tree.png"), &MissingAsset("rock.png")]);
+/// ```
+pub struct EmitDeduped<E>(pub E);
+
+impl<U: From<T>, T: fmt::Debug> From<T> for EmitDeduped<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+impl<E: Event + PartialEq> Failure for EmitDeduped<E> {
+    type Param = (Option<SResMut<DedupedEvents<E>>>, SysfailHooks<'static>);
 
     const LEVEL: Level = Level::INFO;
 
-    fn handle_error(self, mut event_writer: EventWriter<E>, _: Option<&'static impl Callsite>) {
-        event_writer.send(self.0);
+    fn handle_error(
+        self,
+        (pending, hooks): <Self::Param as SystemParam>::Item<'_, '_>,
+        _: Option<&'static impl Callsite>,
+    ) {
+        if !hooks.enabled() {
+            return;
+        }
+        let Some(mut pending) = pending else {
+            panic!(
+                "`EmitDeduped<{event}>` is used in a sysfail system, but the `DedupedEvents` \
+                resource doesn't exist. Use `SysfailPlugin::default().with_deduped_event::<{event}>()`.",
+                event = type_name::<E>(),
+            )
+        };
+        if !pending.0.contains(&self.0) {
+            pending.0.push(self.0);
+        }
     }
 }
@@ -0,0 +1,58 @@
+use bevy_ecs::system::Resource;
+
+/// Whether `sysfail` systems handle their errors, `true` by default.
+///
+/// This resource is optional. When it is absent, errors are handled as usual.
+/// Insert `SysfailEnabled(false)` to skip handling errors in all `sysfail`
+/// systems, for example in benchmarks: nothing is logged or emitted. Systems
+/// still stop at the first error, since `?` still returns from the system
+/// body. Changes to the resource take effect the next time an error is
+/// returned.
+///
+/// The logging `Failure`s of this crate, such as `Log` and `LogSimply`, the
+/// `Emit` family, `Panic` and `StoreLast` read it through
+/// [`SysfailHooks`](crate::SysfailHooks) in their `Failure::Param`, so a
+/// system using them can't have a `ResMut<SysfailEnabled>` parameter.
+/// `Aggregate`, `DiagnoseErrors`, `Eprintln`, `LogToComponent`,
+/// `ReportSentry`, `RunCommand` and `SendTo` don't check it.
+///
+/// `sysfail_handle!` reads it once, before running the system body. In
+/// regular systems, this adds a `SysfailHooks` parameter to the system. It is
+/// not read by the `sysfail_handle!` of [`sysfail_block!`](crate::sysfail_block),
+/// which has no access to the `World`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+/// use bevy_mod_sysfail::{prelude::*, testing::capture_logs, SysfailEnabled};
+///
+/// #[derive(Resource, Default)]
+/// struct Reached(bool);
+///
+/// #[sysfail(LogSimply<&'static str>)]
+/// fn failable_system(mut reached: ResMut<Reached>) {
+///     let () = Err("Not logged while disabled")?;
+///     reached.0 = true;
+/// }
+/// let mut world = World::new();
+/// world.init_resource::<Reached>();
+/// world.insert_resource(SysfailEnabled(false));
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems(failable_system);
+///
+/// assert!(capture_logs(|| schedule.run(&mut world)).is_empty());
+/// // The system still returned at the `?`
+/// assert!(!world.resource::<Reached>().0);
+///
+/// world.resource_mut::<SysfailEnabled>().0 = true;
+/// assert_eq!(capture_logs(|| schedule.run(&mut world)).len(), 1);
+/// ```
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SysfailEnabled(pub bool);
+impl Default for SysfailEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
@@ -0,0 +1,50 @@
+use std::fmt;
+
+use crate::{Callsite, Failure, Level};
+
+/// Print the error to `stderr` with `eprintln!`, bypassing `tracing`.
+///
+/// The message includes the system name and the file and line of the
+/// system. This works without any tracing subscriber, so it is handy in
+/// small examples and tests. **This is not meant for production**, use
+/// [`Log`](crate::prelude::Log) instead, which is deduplicated and can be
+/// filtered.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::{ecs::system::RunSystemOnce, prelude::*};
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail(Eprintln<&'static str>)]
+/// fn failable_system() {
+///     let () = Err("Printed to stderr")?;
+/// }
+/// // Prints "src/main.rs:5 my_crate::failable_system: Printed to stderr"
+/// World::new().run_system_once(failable_system);
+/// ```
+pub struct Eprintln<T>(pub T);
+
+impl<U: From<T>, T: fmt::Debug> From<T> for Eprintln<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+impl<T: fmt::Display> Failure for Eprintln<T> {
+    type Param = ();
+
+    const LEVEL: Level = Level::WARN;
+
+    fn handle_error(self, (): (), callsite: Option<&'static impl Callsite>) {
+        let Some(callsite) = callsite else {
+            eprintln!("{}", self.0);
+            return;
+        };
+        let meta = callsite.metadata();
+        let file = meta.file().unwrap_or("<unknown>");
+        let line = meta.line().unwrap_or(0);
+        let system = meta.module_path().unwrap_or("<unknown>");
+        eprintln!("{file}:{line} {system}: {}", self.0);
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::system::{lifetimeless::SQuery, Local, SystemParam};
+use bevy_utils::tracing::warn;
+
+use crate::{Callsite, Failure, Level};
+
+/// A list of errors, stored in a component, for [`LogToComponent`].
+///
+/// Spawn an entity with this component, for example to display the errors
+/// in the UI. When `errors` has more than `max_len` errors, the oldest
+/// ones are removed.
+#[derive(Component, Debug, Clone)]
+pub struct ErrorLog {
+    /// The formatted errors, the most recent last.
+    pub errors: Vec<String>,
+    /// The maximum length of `errors`.
+    pub max_len: usize,
+}
+impl ErrorLog {
+    /// An empty `ErrorLog` keeping up to `max_len` errors.
+    #[must_use]
+    pub const fn new(max_len: usize) -> Self {
+        Self { errors: Vec::new(), max_len }
+    }
+    fn push(&mut self, error: String) {
+        self.errors.push(error);
+        let excess = self.errors.len().saturating_sub(self.max_len);
+        self.errors.drain(..excess);
+    }
+}
+impl Default for ErrorLog {
+    /// An empty `ErrorLog` keeping up to 100 errors.
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+/// Push the formatted error to the [`ErrorLog`] component.
+///
+/// If there are several entities with an `ErrorLog`, the error is pushed to
+/// all of them. If there are none, the error is discarded, and a warning is
+/// logged the first time it happens.
+///
+/// This doesn't deduplicate errors, use [`Tee`](crate::prelude::Tee) with
+/// [`Log`](crate::prelude::Log) to also log them, or
+/// [`Sampled`](crate::prelude::Sampled) to push only some of them.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail(Tee<Log<&'static str>, LogToComponent<&'static str>>)]
+/// fn failable_system() {
+///     let () = Err("Pushed to the ErrorLog")?;
+/// }
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .add_systems(Update, failable_system);
+/// let error_log = app.world.spawn(ErrorLog::new(10)).id();
+/// app.update();
+/// let errors = &app.world.get::<ErrorLog>(error_log).unwrap().errors;
+/// assert_eq!(errors, &["Pushed to the ErrorLog"]);
+/// ```
+pub struct LogToComponent<T>(pub T);
+
+impl<U: From<T>, T: fmt::Debug> From<T> for LogToComponent<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+impl<T: fmt::Display> Failure for LogToComponent<T> {
+    type Param = (SQuery<&'static mut ErrorLog>, Local<'static, bool>);
+
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(
+        self,
+        (mut logs, mut warned): <Self::Param as SystemParam>::Item<'_, '_>,
+        _: Option<&'static impl Callsite>,
+    ) {
+        if logs.is_empty() {
+            if !*warned {
+                *warned = true;
+                warn!("There is no `ErrorLog` entity, errors are discarded");
+            }
+            return;
+        }
+        let error = self.0.to_string();
+        for mut log in &mut logs {
+            log.push(error.clone());
+        }
+    }
+}
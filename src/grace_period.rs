@@ -0,0 +1,102 @@
+use std::fmt;
+
+use bevy_ecs::system::{Local, SystemParam};
+use bevy_ecs::world::World;
+
+use crate::{Callsite, Failure, Level};
+
+/// Ignore the errors of the first `RUNS` runs of the system, then handle
+/// errors with `F`.
+///
+/// This is useful for systems that legitimately fail for a few frames at
+/// startup, for example while assets are loading. Runs are counted whether
+/// the system fails or not, so for a system running every frame, this is
+/// the number of frames. Each system has its own count.
+///
+/// After the grace period, this behaves exactly like `F`.
+///
+/// ```rust
+/// use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+/// use bevy_mod_sysfail::{prelude::*, testing::capture_logs};
+///
+/// #[sysfail(GracePeriod<LogSimply<&'static str>, 3>)]
+/// fn failable_system() {
+///     let () = Err("Asset not loaded")?;
+/// }
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems(failable_system);
+/// let logs = capture_logs(|| (0..5).for_each(|_| schedule.run(&mut world)));
+/// // The 4th and 5th errors
+/// assert_eq!(logs.len(), 2);
+/// ```
+/// Wrap a [`Log`](crate::prelude::Log) to deduplicate errors after the
+/// grace period:
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail(GracePeriod<Log<&'static str>, 1>)]
+/// fn failable_system() {
+///     let () = Err("Asset not loaded")?;
+/// }
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins).add_systems(Update, failable_system);
+/// // Ignored
+/// app.update();
+/// // Logged by `Log`
+/// app.update();
+/// ```
+pub struct GracePeriod<F, const RUNS: u32>(pub F);
+
+impl<T: fmt::Debug, F: From<T>, const RUNS: u32> From<T> for GracePeriod<F, RUNS> {
+    fn from(t: T) -> Self {
+        Self(F::from(t))
+    }
+}
+
+/// Count a run of the system, returns whether it is still in the grace period.
+fn in_grace(runs: &mut u32, grace: u32) -> bool {
+    let in_grace = *runs < grace;
+    if in_grace {
+        *runs += 1;
+    }
+    in_grace
+}
+
+impl<F: Failure, const RUNS: u32> Failure for GracePeriod<F, RUNS> {
+    type Param = (F::Param, Local<'static, u32>);
+
+    const LEVEL: Level = F::LEVEL;
+
+    fn handle_error(
+        self,
+        (param, mut runs): <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        if !in_grace(&mut runs, RUNS) {
+            self.0.handle_error(param, callsite);
+        }
+    }
+
+    fn handle_error_fallible(
+        self,
+        (param, mut runs): <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if in_grace(&mut runs, RUNS) {
+            return Ok(());
+        }
+        self.0.handle_error_fallible(param, callsite)
+    }
+
+    fn on_success((param, mut runs): <Self::Param as SystemParam>::Item<'_, '_>) {
+        in_grace(&mut runs, RUNS);
+        F::on_success(param);
+    }
+
+    fn init(world: &mut World) {
+        F::init(world);
+    }
+}
@@ -0,0 +1,107 @@
+use bevy_ecs::component::Tick;
+use bevy_ecs::system::{ReadOnlySystemParam, Res, SystemMeta, SystemParam};
+use bevy_ecs::world::{unsafe_world_cell::UnsafeWorldCell, World};
+
+use crate::{Level, StatelessParam, SysfailEnabled, SysfailLevelCounts};
+
+type HooksParam = (
+    Option<Res<'static, SysfailEnabled>>,
+    Option<Res<'static, SysfailLevelCounts>>,
+);
+
+/// The optional [`SysfailEnabled`] and [`SysfailLevelCounts`] resources, read
+/// by the logging `Failure`s of this crate and by `Emit`.
+///
+/// Add it to the [`Failure::Param`] of your own `Failure`s to also honor
+/// them. It can be built from a `&World`, so such `Failure`s can still be
+/// used in exclusive systems, see [`StatelessParam`].
+///
+/// [`Failure::Param`]: crate::Failure::Param
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, Callsite, Level, SysfailEnabled, SysfailHooks};
+///
+/// struct Print(&'static str);
+/// impl From<&'static str> for Print {
+///     fn from(value: &'static str) -> Self {
+///         Self(value)
+///     }
+/// }
+/// impl Failure for Print {
+///     type Param = SysfailHooks<'static>;
+///     const LEVEL: Level = Level::INFO;
+///
+///     fn handle_error(self, hooks: SysfailHooks, _: Option<&'static impl Callsite>) {
+///         if hooks.enabled() {
+///             hooks.count(Level::INFO);
+///             println!("{}", self.0);
+///         }
+///     }
+/// }
+///
+/// #[exclusive_sysfail(Print)]
+/// fn exclusive_system(_world: &mut World) {
+///     let () = Err("Printed when enabled")?;
+/// }
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct SysfailHooks<'w> {
+    enabled: Option<&'w SysfailEnabled>,
+    counts: Option<&'w SysfailLevelCounts>,
+}
+impl SysfailHooks<'_> {
+    /// Whether to handle errors, `false` only if [`SysfailEnabled`] exists
+    /// and is `false`.
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.enabled.map_or(true, |enabled| enabled.0)
+    }
+    /// Count an error logged at `level`, if [`SysfailLevelCounts`] exists.
+    pub fn count(&self, level: Level) {
+        if let Some(counts) = self.counts {
+            counts.count(level);
+        }
+    }
+}
+
+// SAFETY: This has the same access as `HooksParam`, through its `init_state`.
+unsafe impl SystemParam for SysfailHooks<'_> {
+    type State = <HooksParam as SystemParam>::State;
+    type Item<'w, 's> = SysfailHooks<'w>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        HooksParam::init_state(world, system_meta)
+    }
+
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: The caller upholds the requirements of `HooksParam`.
+        let (enabled, counts) =
+            unsafe { HooksParam::get_param(state, system_meta, world, change_tick) };
+        SysfailHooks {
+            enabled: enabled.map(Res::into_inner),
+            counts: counts.map(Res::into_inner),
+        }
+    }
+}
+// SAFETY: `HooksParam` is read-only.
+unsafe impl ReadOnlySystemParam for SysfailHooks<'_> {}
+
+impl StatelessParam for SysfailHooks<'_> {
+    fn from_world(world: &World) -> SysfailHooks<'_> {
+        SysfailHooks {
+            enabled: world.get_resource(),
+            counts: world.get_resource(),
+        }
+    }
+    fn detached() -> SysfailHooks<'static> {
+        SysfailHooks::default()
+    }
+}
@@ -1,6 +1,26 @@
+use std::convert::Infallible;
+
 use crate::{Callsite, Failure, Level};
 
 /// Do nothing with errors in `#[sysfail]` systems.
+///
+/// `#[sysfail(Ignore)]` discards the errors of `?` without converting them,
+/// so it works with any error type, even those not implementing `Debug`:
+///
+/// ```rust
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// struct NotDebug;
+///
+/// #[sysfail(Ignore)]
+/// fn failable_system() {
+///     let () = Err(NotDebug)?;
+///     Err(NotDebug)
+/// }
+/// ```
+///
+/// The `From<T: Debug>` implementation is used for errors returned in other
+/// ways, such as `return Err(error.into())` or in `sysfail_handle!`.
 pub struct Ignore;
 
 impl<T: std::fmt::Debug> From<T> for Ignore {
@@ -16,3 +36,15 @@ impl Failure for Ignore {
 
     fn handle_error(self, (): (), _: Option<&'static impl Callsite>) {}
 }
+
+/// `#[sysfail(Infallible)]` systems can't return errors, see
+/// [`sysfail`](crate::sysfail#infallible-systems).
+impl Failure for Infallible {
+    type Param = ();
+
+    const LEVEL: Level = Level::TRACE;
+
+    fn handle_error(self, (): (), _: Option<&'static impl Callsite>) {
+        match self {}
+    }
+}
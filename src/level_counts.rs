@@ -0,0 +1,91 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use bevy_ecs::system::Resource;
+
+use crate::Level;
+
+/// The index of `level` in [`SysfailLevelCounts::counts`], as in [`u8_to_level`].
+///
+/// [`u8_to_level`]: crate::u8_to_level
+const fn level_index(level: Level) -> usize {
+    match level {
+        Level::TRACE => 0,
+        Level::DEBUG => 1,
+        Level::INFO => 2,
+        Level::WARN => 3,
+        _ => 4,
+    }
+}
+
+/// The number of errors logged by `sysfail` systems, per log level, for
+/// example for a health endpoint.
+///
+/// This resource is optional, insert it with `init_resource` or
+/// `SysfailPlugin::count_errors` to start counting. Errors are counted by the
+/// logging `Failure`s of this crate when they decide to log them, at their
+/// level: errors deduplicated by [`Log`](crate::prelude::Log) or skipped by
+/// `LogThrottled` and `LogOnChange` aren't counted, but errors filtered out by
+/// the level filter or the subscriber are. They read it through
+/// [`SysfailHooks`](crate::SysfailHooks), so errors handled with
+/// `sysfail_handle!` aren't counted.
+///
+/// The counters are atomics, so this is only read through a `Res`, and
+/// doesn't prevent systems from running in parallel. Use [`reset`] to start
+/// counting from zero again, for example after reporting them.
+///
+/// [`reset`]: Self::reset
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+/// use bevy_mod_sysfail::{prelude::*, testing::capture_logs, Level, SysfailLevelCounts};
+///
+/// #[sysfail(LogSimply<&'static str, Error>)]
+/// fn failable_system() {
+///     let () = Err("Counted")?;
+/// }
+/// #[sysfail(LogSimply<&'static str, Info>)]
+/// fn informative_system() {
+///     let () = Err("Counted as info")?;
+/// }
+/// let mut world = World::new();
+/// world.init_resource::<SysfailLevelCounts>();
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems((failable_system, informative_system));
+/// capture_logs(|| (0..3).for_each(|_| schedule.run(&mut world)));
+///
+/// let counts = world.resource::<SysfailLevelCounts>();
+/// assert_eq!(counts.counts(), [0, 0, 3, 0, 3]);
+/// assert_eq!(counts.get(Level::ERROR), 3);
+///
+/// counts.reset();
+/// assert_eq!(counts.counts(), [0; 5]);
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct SysfailLevelCounts {
+    counts: [AtomicU64; 5],
+}
+impl SysfailLevelCounts {
+    /// The number of errors per level, from `TRACE` (`0`) to `ERROR` (`4`).
+    #[must_use]
+    pub fn counts(&self) -> [u64; 5] {
+        [0, 1, 2, 3, 4].map(|i| self.counts[i].load(Ordering::Relaxed))
+    }
+    /// The number of errors logged at `level`.
+    #[must_use]
+    pub fn get(&self, level: Level) -> u64 {
+        self.counts[level_index(level)].load(Ordering::Relaxed)
+    }
+    /// Set all the counts to zero.
+    pub fn reset(&self) {
+        for count in &self.counts {
+            count.store(0, Ordering::Relaxed);
+        }
+    }
+    /// Count one error logged at `level`.
+    pub(crate) fn count(&self, level: Level) {
+        self.counts[level_index(level)].fetch_add(1, Ordering::Relaxed);
+    }
+}
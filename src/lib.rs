@@ -1,58 +1,629 @@
 /*!
 [`Dedup`]: Dedup
+[`StatelessParam`]: StatelessParam
+[`RoutedLog`]: prelude::RoutedLog
+[`CaughtPanic`]: CaughtPanic
+[`NoneError`]: NoneError
+[`FailureMode`]: FailureMode
+[`Configured`]: prelude::Configured
 [`Failure`]: Failure
 [`sysfail`]: sysfail
 [`Emit`]: prelude::Emit
+[`EmitFor`]: prelude::EmitFor
 [`Log`]: prelude::Log
 [`LogSimply`]: prelude::LogSimply
 [`Ignore`]: prelude::Ignore
+[`Panic`]: prelude::Panic
+[`Sampled`]: prelude::Sampled
+[`Tee`]: prelude::Tee
 [`Event`]: bevy_ecs::event::Event
 */
 #![doc = include_str!("../README.md")]
 #![warn(clippy::pedantic)]
 
-use bevy_ecs::system::SystemParam;
+use bevy_ecs::{system::SystemParam, world::World};
 
-/// See the [`crate`]-level documentation for usage and examples.
+/// Handle the errors returned with `?` in a bevy system.
+///
+/// See the [`crate`]-level documentation for the available `Failure`s and
+/// [options](crate#options).
 pub use bevy_mod_sysfail_macros::sysfail;
 
 /// See the [`crate`]-level documentation for usage and examples.
+///
+/// The `&mut World` parameter can be used freely in the body, including
+/// with `?` in `if let` and `match` arms borrowing the world:
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, testing::run_and_capture};
+///
+/// #[derive(Resource)]
+/// struct Health(u32);
+///
+/// #[exclusive_sysfail(LogSimply<&'static str>)]
+/// fn take_damage(world: &mut World) {
+///     if let Some(mut health) = world.get_resource_mut::<Health>() {
+///         health.0 = health.0.checked_sub(30).ok_or("Dead")?;
+///     }
+///     match world.get_resource::<Health>() {
+///         Some(health) if health.0 < 50 => Err("Low health")?,
+///         Some(_) => {}
+///         None => world.insert_resource(Health(100)),
+///     }
+///     world.resource_mut::<Health>().0 += 1;
+/// }
+/// let mut world = World::new();
+/// assert!(run_and_capture(&mut world, take_damage).is_empty());
+/// assert_eq!(world.resource::<Health>().0, 101);
+/// run_and_capture(&mut world, take_damage);
+/// let logs = run_and_capture(&mut world, take_damage);
+/// assert_eq!(logs[0].1, "Low health");
+/// assert_eq!(world.resource::<Health>().0, 42);
+/// run_and_capture(&mut world, take_damage);
+/// let logs = run_and_capture(&mut world, take_damage);
+/// assert_eq!(logs[0].1, "Dead");
+/// ```
+///
+/// The system must have a `&mut World` parameter:
+/// ```rust,compile_fail
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// // error: #[exclusive_sysfail] systems must have a `&mut World` parameter
+/// #[exclusive_sysfail(LogSimply<&'static str>)]
+/// fn exclusive_system(world: &World) {}
+/// ```
 pub use bevy_mod_sysfail_macros::exclusive_sysfail;
-pub use bevy_utils::tracing::{Callsite, Level};
+
+/// Apply `#[sysfail]` to all functions in a module.
+///
+/// The attribute argument is the default `Failure` of systems in the module.
+/// Functions with their own `#[sysfail]` or `#[exclusive_sysfail]` attribute
+/// are left as is. Use `#[sysfail(skip)]` to exclude a function that is not
+/// a system.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail_mod(Log<&'static str, Error>)]
+/// mod systems {
+///     use bevy::prelude::*;
+///     use bevy_mod_sysfail::prelude::*;
+///
+///     pub fn move_things(query: Query<&mut Transform>) {
+///         let () = Err("Failed to move things")?;
+///     }
+///
+///     #[sysfail(Ignore)]
+///     pub fn delete_things(mut commands: Commands) {
+///         let () = Err(0_u32)?;
+///     }
+///
+///     pub fn gen_system<T: Component>(query: Query<&T>) {
+///         let () = Err("Generic failure")?;
+///     }
+///
+///     #[sysfail(skip)]
+///     pub fn helper() -> u32 {
+///         10
+///     }
+/// }
+/// systems::helper();
+/// ```
+pub use bevy_mod_sysfail_macros::sysfail_mod;
+
+/// Handle errors in a block inside a regular system.
+///
+/// This is useful when only a portion of a system is fallible. Since
+/// `sysfail_block!` can't add parameters to the system, you need to add the
+/// `Failure::Param` parameter yourself, and pass it to `sysfail_block!`.
+///
+/// The first argument is only evaluated when the block returns an error.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy::ecs::system::StaticSystemParam;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// type MyFailure = Log<&'static str, Info>;
+///
+/// fn partially_failable(
+///     query: Query<&Transform>,
+///     failure_param: StaticSystemParam<<MyFailure as Failure>::Param>,
+/// ) {
+///     println!("This always runs");
+///     sysfail_block!(failure_param.into_inner(), MyFailure, {
+///         let () = Err("Only this block failed")?;
+///     });
+///     println!("This also always runs");
+/// }
+/// ```
+pub use bevy_mod_sysfail_macros::sysfail_block;
+pub use bevy_utils::tracing::{level_filters::LevelFilter, Callsite, Level};
+#[cfg(feature = "full")]
+pub use config::{SysfailConfig, SysfailLevelOverrides};
+pub use configured::FailureMode;
 pub use dedup::Dedup;
-pub use log_levels::LogLevelModifier;
+#[cfg(feature = "bevy_diagnostic")]
+pub use diagnose::ErrorDiagnostic;
+pub use emit::EventTuple;
+pub use enabled::SysfailEnabled;
+pub use hooks::SysfailHooks;
+pub use level_counts::SysfailLevelCounts;
+#[cfg(feature = "full")]
+pub use log::Redact;
+pub use log_levels::{level_to_modifier, parse_level, u8_to_level, LogLevelModifier};
+pub use log_routed::LeveledError;
+pub use none_error::NoneError;
+#[cfg(feature = "full")]
+pub use observer::SysfailObserver;
+pub use panic::CaughtPanic;
+#[cfg(feature = "registry")]
+pub use registry::{sysfail_sites, SysfailSite};
+pub use run_command::OnError;
+pub use sampled::DedupFailure;
+pub use unit_error::UnitError;
+pub use warn_missing::WarnMissing;
 
+mod aggregate;
+#[cfg(feature = "full")]
+mod config;
+mod configured;
+mod context;
 mod dedup;
+mod deferred;
+#[cfg(feature = "bevy_diagnostic")]
+mod diagnose;
+mod dispatch;
 mod emit;
+mod enabled;
+mod eprintln;
+mod error_log;
+mod grace_period;
+mod hooks;
 mod ignore;
+mod level_counts;
 #[cfg(feature = "full")]
 mod log;
+#[cfg(feature = "json")]
+mod log_json;
 mod log_levels;
+mod log_on_change;
+mod log_routed;
 mod log_simple;
+#[cfg(feature = "otel")]
+mod log_span_error;
+mod log_throttled;
+mod log_timed;
+mod none_error;
+#[cfg(feature = "full")]
+mod observer;
+mod panic;
+#[cfg(feature = "full")]
+mod plugin;
+#[cfg(feature = "registry")]
+mod registry;
+#[cfg(feature = "sentry")]
+mod report_sentry;
+mod run_command;
+mod sampled;
+#[cfg(feature = "channel")]
+mod send_to;
+mod store_last;
+mod tee;
+pub mod testing;
+mod unit_error;
+mod warn_missing;
 
 /// Useful set of [`Failure`] default implementations and [`LogLevelModifier`]s.
 pub mod prelude {
-    pub use crate::emit::Emit;
+    pub use crate::aggregate::{report_aggregated, Aggregate, SysfailAggregator};
+    pub use crate::configured::Configured;
+    pub use crate::deferred::{flush_deferred, Defer, DeferredErrors};
+    #[cfg(feature = "bevy_diagnostic")]
+    pub use crate::diagnose::DiagnoseErrors;
+    pub use crate::emit::{
+        flush_deduped_events, DedupedEvents, Emit, EmitDeduped, EmitFor, EmitMany, EmitWithSite,
+        EntityError, EventSender, ForEntity, IntoEvents, SysfailEvent,
+    };
+    pub use crate::eprintln::Eprintln;
+    pub use crate::error_log::{ErrorLog, LogToComponent};
+    pub use crate::grace_period::GracePeriod;
     pub use crate::ignore::Ignore;
     #[cfg(feature = "full")]
-    pub use crate::log::Log;
-    pub use crate::log_levels::{Debug, Error, Info, Trace, Warn};
-    pub use crate::log_simple::LogSimply;
-    pub use crate::{exclusive_sysfail, sysfail, Failure};
+    pub use crate::log::{
+        Log, LogN, LogOrPanic, LogPerSite, LogRedacted, LogShared, LogVerbose, LogWith, SharedDedup,
+    };
+    #[cfg(feature = "json")]
+    pub use crate::log_json::LogJson;
+    pub use crate::log_levels::{Debug, Error, Info, LevelN, Trace, Warn};
+    pub use crate::log_on_change::LogOnChange;
+    pub use crate::log_routed::RoutedLog;
+    pub use crate::log_simple::{LogOnce, LogSimply};
+    #[cfg(feature = "otel")]
+    pub use crate::log_span_error::LogSpanError;
+    pub use crate::log_throttled::LogThrottled;
+    pub use crate::log_timed::LogTimed;
+    pub use crate::panic::Panic;
+    #[cfg(feature = "full")]
+    pub use crate::plugin::SysfailPlugin;
+    #[cfg(feature = "sentry")]
+    pub use crate::report_sentry::ReportSentry;
+    pub use crate::run_command::RunCommand;
+    pub use crate::sampled::Sampled;
+    #[cfg(feature = "channel")]
+    pub use crate::send_to::{SendTo, SysfailSender};
+    pub use crate::store_last::{LastError, StoreLast};
+    pub use crate::tee::Tee;
+    pub use crate::{
+        exclusive_sysfail, sysfail, sysfail_block, sysfail_mod, ExclusiveFailure, Failure,
+    };
 }
 
 /// Symbols for the `sysfail` attribute macro.
 #[doc(hidden)]
 pub mod __macro {
-    pub use crate::Failure;
+    pub use crate::context::{CfgTest, Context};
+    pub use crate::log_routed::LeveledCallsites;
+    pub use crate::log_throttled::SystemRuns;
+    #[cfg(feature = "registry")]
+    pub use crate::registry::{SysfailSite, SYSFAIL_SITES};
+    pub use crate::CaughtPanic;
+    pub use crate::{
+        ExclusiveFailure, Failure, Level, LogLevelModifier, StatelessParam, SysfailHooks,
+    };
+    pub use bevy_ecs::schedule::{DynEq, SystemSet};
     pub use bevy_ecs::system::StaticSystemParam;
     pub use bevy_utils::tracing::callsite::{DefaultCallsite, Identifier};
     pub use bevy_utils::tracing::{field::FieldSet, metadata, Metadata};
+    pub use bevy_utils::Instant;
+    #[cfg(feature = "registry")]
+    pub use linkme as __linkme;
+
+    /// The `Failure` of `#[sysfail]` without a `Failure` type.
+    #[cfg(feature = "full")]
+    pub type DefaultLog = crate::prelude::Log<Box<dyn std::error::Error>>;
+    /// The `Failure` of `#[sysfail]` without a `Failure` type.
+    ///
+    /// `Log` requires the `full` feature, so we fallback to `LogSimply`.
+    #[cfg(not(feature = "full"))]
+    pub type DefaultLog = crate::prelude::LogSimply<Box<dyn std::error::Error>>;
+
+    /// A `Failure` handled by a [`FailureMode`](crate::FailureMode) value,
+    /// such as `Configured`.
+    pub trait Configure {
+        /// The error handled by the mode.
+        type Error;
+        /// Set the mode of the `mode_ref` option.
+        fn set_mode(&mut self, mode: &'static dyn crate::FailureMode<Self::Error>);
+    }
+
+    /// The `Failure` `F` of a `sysfail` system, to check which optional
+    /// features it implements.
+    ///
+    /// Like [`Trailing`], this uses autoref specialization: the methods of
+    /// traits implemented for `Probe<F>` when `F` implements a feature take
+    /// precedence over the fallbacks implemented for `&Probe<F>`.
+    pub struct Probe<F>(pub std::marker::PhantomData<F>);
+
+    /// A `Failure` sampling the runs of its system, such as `LogThrottled`.
+    pub trait CountRuns {
+        /// Set the run counter of the system returning this error.
+        fn set_runs(&mut self, runs: &'static SystemRuns);
+    }
+    /// [`Probe`] for [`CountRuns`] failures.
+    pub trait ProbeRuns<F> {
+        /// [`CountRuns::set_runs`], if `F` implements it.
+        fn count_runs(self) -> Option<fn(&mut F, &'static SystemRuns)>;
+    }
+    impl<F: CountRuns> ProbeRuns<F> for Probe<F> {
+        fn count_runs(self) -> Option<fn(&mut F, &'static SystemRuns)> {
+            Some(F::set_runs)
+        }
+    }
+    /// The fallback of [`ProbeRuns`], for failures that don't count runs.
+    pub trait NoRuns<F> {
+        /// Always `None`.
+        fn count_runs(self) -> Option<fn(&mut F, &'static SystemRuns)>;
+    }
+    impl<F> NoRuns<F> for &Probe<F> {
+        fn count_runs(self) -> Option<fn(&mut F, &'static SystemRuns)> {
+            None
+        }
+    }
+
+    /// A `Failure` recording how long the system ran, such as `LogTimed`.
+    pub trait Timed {
+        /// Set the time between the start of the system body and the error.
+        fn set_elapsed(&mut self, elapsed: bevy_utils::Duration);
+    }
+    /// The start of a system and the [`Timed::set_elapsed`] of its `Failure`.
+    pub type Timer<F> = (Instant, fn(&mut F, bevy_utils::Duration));
+    /// [`Probe`] for [`Timed`] failures.
+    pub trait ProbeTimed<F> {
+        /// Start timing the system, if `F` implements [`Timed`].
+        fn start_timer(self) -> Option<Timer<F>>;
+    }
+    impl<F: Timed> ProbeTimed<F> for Probe<F> {
+        fn start_timer(self) -> Option<Timer<F>> {
+            Some((Instant::now(), F::set_elapsed))
+        }
+    }
+    /// The fallback of [`ProbeTimed`], for failures that aren't timed.
+    pub trait NoTimer<F> {
+        /// Always `None`.
+        fn start_timer(self) -> Option<Timer<F>>;
+    }
+    impl<F> NoTimer<F> for &Probe<F> {
+        fn start_timer(self) -> Option<Timer<F>> {
+            None
+        }
+    }
+
+    /// The value of a `return value;` in a `sysfail` system body.
+    pub trait SystemReturn<F> {
+        /// Convert `self` into the result of the system body.
+        ///
+        /// # Errors
+        ///
+        /// When `self` is an `Err`.
+        fn into_result(self) -> Result<(), F>;
+    }
+    /// Convert `Option`s into `Result`s, for the `?` of `none_error` systems.
+    pub trait TryValue {
+        /// The value of `Some` or `Ok`.
+        type Value;
+        /// The error of `Err`, or `NoneError` for `Option`s.
+        type Error;
+        /// `self` as a `Result`.
+        ///
+        /// # Errors
+        ///
+        /// When `self` is an `Err` or `None`.
+        fn into_result(self) -> Result<Self::Value, Self::Error>;
+    }
+    impl<T, E> TryValue for Result<T, E> {
+        type Value = T;
+        type Error = E;
+        fn into_result(self) -> Self {
+            self
+        }
+    }
+    impl<T> TryValue for Option<T> {
+        type Value = T;
+        type Error = crate::NoneError;
+        #[track_caller]
+        fn into_result(self) -> Result<T, crate::NoneError> {
+            // Not in a closure, which would lose the `#[track_caller]` location
+            let location = std::panic::Location::caller();
+            self.ok_or(crate::NoneError { location })
+        }
+    }
+
+    /// Discard the error of a `Result`, for the `?` of `#[sysfail(Ignore)]`
+    /// system bodies, without requiring a `From` implementation.
+    pub trait IgnoreErr {
+        /// The `Ok` value.
+        type Ok;
+        /// Replace the error by `Ignore`.
+        ///
+        /// # Errors
+        ///
+        /// When `self` is an `Err`.
+        fn ignore_err(self) -> Result<Self::Ok, crate::prelude::Ignore>;
+    }
+    impl<T, E> IgnoreErr for Result<T, E> {
+        type Ok = T;
+        fn ignore_err(self) -> Result<T, crate::prelude::Ignore> {
+            self.map_err(|_| crate::prelude::Ignore)
+        }
+    }
+    impl<F> SystemReturn<F> for () {
+        fn into_result(self) -> Result<(), F> {
+            Ok(())
+        }
+    }
+    impl<F: From<E>, E> SystemReturn<F> for Result<(), E> {
+        // So that `LogPerSite` records the location of the trailing expression
+        #[track_caller]
+        fn into_result(self) -> Result<(), F> {
+            match self {
+                Ok(()) => Ok(()),
+                Err(err) => Err(F::from(err)),
+            }
+        }
+    }
+
+    /// The trailing expression of a `sysfail` system body, of type `T`, in
+    /// a system handling errors with `F`.
+    ///
+    /// `Trailing(expr, PhantomData).into_result()` calls
+    /// [`TrailingResult::into_result`] when `T` is a `Result<(), E>`, and
+    /// [`TrailingValue::into_result`] otherwise, since the latter is only
+    /// implemented for `&Trailing`.
+    pub struct Trailing<F, T>(pub T, pub std::marker::PhantomData<F>);
+
+    /// A trailing `Result<(), E>`, handled as if it was followed by `?`.
+    pub trait TrailingResult {
+        /// The error of the trailing `Result`.
+        type Error;
+        /// The trailing `Result`.
+        ///
+        /// # Errors
+        ///
+        /// When the trailing expression is an `Err`.
+        fn into_result(self) -> Result<(), Self::Error>;
+    }
+    // No bounds on `E`, so that a missing `From<E>` is an error, rather than
+    // a fallback to `TrailingValue`, which would discard the error.
+    impl<F, E> TrailingResult for Trailing<F, Result<(), E>> {
+        type Error = E;
+        fn into_result(self) -> Result<(), E> {
+            self.0
+        }
+    }
+
+    /// Other trailing expressions, discarded as if they were followed by `;`.
+    pub trait TrailingValue {
+        /// The `Failure` of the system.
+        type Error;
+        /// Always `Ok(())`.
+        ///
+        /// # Errors
+        ///
+        /// Never.
+        fn into_result(self) -> Result<(), Self::Error>;
+    }
+    impl<F, T> TrailingValue for &Trailing<F, T> {
+        type Error = F;
+        fn into_result(self) -> Result<(), F> {
+            Ok(())
+        }
+    }
+
+    /// Convert the error of `result` into `F`, for the `?` of `sysfail` system
+    /// bodies.
+    ///
+    /// The macro spans the `F` of the call at the failure type of the attribute,
+    /// so that a missing conversion is reported there, and the call itself at
+    /// the `?` expression, for `LogPerSite`.
+    ///
+    /// # Errors
+    ///
+    /// When `result` is an `Err`.
+    #[track_caller]
+    #[inline]
+    pub fn into_failure<F: From<E>, T, E>(result: Result<T, E>) -> Result<T, F> {
+        match result {
+            Ok(value) => Ok(value),
+            Err(err) => Err(F::from(err)),
+        }
+    }
+
+    /// Log an error returned by [`Failure::handle_error_fallible`].
+    ///
+    /// This doesn't use a `Failure`, so that it can't fail itself.
+    pub fn handler_failed(system: &str, error: &(dyn std::error::Error + 'static)) {
+        bevy_utils::tracing::error!("Failed to handle the error of {system}: {error}");
+    }
 }
 
 /// The `Err` side of the return type of `#[sysfail]`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a `Failure`, it can't be used as the error type of `#[sysfail]`",
+    label = "not a `Failure`",
+    note = "use one of the `Failure`s of `bevy_mod_sysfail::prelude`, such as `Log<{Self}>`"
+)]
 pub trait Failure {
     /// The system param used by [`Self::handle_error`].
+    ///
+    /// # Conflicts
+    ///
+    /// `#[sysfail]` adds `Param` to the parameters of the system, so, as with
+    /// any system parameter, it can't conflict with the other parameters of
+    /// the system. For example, if both the system and `Param` use
+    /// `ResMut<Foo>`, bevy panics when initializing the system.
+    /// Read-only access, such as the `Res<Time>` of [`Log`](prelude::Log),
+    /// never conflicts with other read-only access.
+    ///
+    /// The `Failure`s of this crate use the least access they need.
+    /// The following have write access:
+    /// - [`Aggregate`](prelude::Aggregate): `ResMut<SysfailAggregator>`.
+    /// - [`Emit<E>`](prelude::Emit) and [`EmitFor<E>`](prelude::EmitFor):
+    ///   `ResMut<Events<E>>`, which conflicts with `EventWriter<E>` and
+    ///   `EventReader<E>`.
+    /// - [`LogShared<T>`](prelude::LogShared): `ResMut<SharedDedup<T>>`.
+    /// - [`LogToComponent`](prelude::LogToComponent): `Query<&mut ErrorLog>`.
+    /// - [`StoreLast`](prelude::StoreLast): `ResMut<LastError>`.
+    /// - `DiagnoseErrors`: `ResMut<DiagnosticsStore>`.
+    ///
+    /// ```rust,should_panic
+    /// use bevy::{ecs::system::RunSystemOnce, prelude::*};
+    /// use bevy_mod_sysfail::prelude::*;
+    ///
+    /// #[derive(Event)]
+    /// struct Hit;
+    ///
+    /// // panics: `EventWriter<Hit>` conflicts with the `ResMut<Events<Hit>>` of `Emit`
+    /// #[sysfail(Emit<Hit>)]
+    /// fn hit_system(mut hits: EventWriter<Hit>) {
+    ///     hits.send(Hit);
+    ///     let () = Err(Hit)?;
+    /// }
+    /// let mut world = World::new();
+    /// world.init_resource::<Events<Hit>>();
+    /// world.run_system_once(hit_system);
+    /// ```
+    /// To share the access between the system and the `Failure`, use a
+    /// [`ParamSet`](bevy_ecs::system::ParamSet) with [`sysfail_block!`]:
+    /// ```rust
+    /// use bevy::{ecs::system::RunSystemOnce, prelude::*};
+    /// use bevy_mod_sysfail::{prelude::*, SysfailHooks};
+    ///
+    /// #[derive(Event)]
+    /// struct Hit;
+    ///
+    /// type EmitParam<'w> = (Option<ResMut<'w, Events<Hit>>>, SysfailHooks<'w>);
+    /// fn hit_system(mut hits: ParamSet<(EventWriter<Hit>, EmitParam)>) {
+    ///     sysfail_block!(hits.p1(), Emit<Hit>, {
+    ///         hits.p0().send(Hit);
+    ///         let () = Err(Hit)?;
+    ///     });
+    /// }
+    /// let mut world = World::new();
+    /// world.init_resource::<Events<Hit>>();
+    /// world.run_system_once(hit_system);
+    /// assert_eq!(world.resource::<Events<Hit>>().len(), 2);
+    /// ```
+    ///
+    /// # Missing resources
+    ///
+    /// Like in any system, a `Res<Foo>` param panics when `Foo` doesn't
+    /// exist. To discard the error instead, use `Option<Res<Foo>>`, and
+    /// [`WarnMissing`] to warn once per system that `Foo` is missing.
+    /// [`SendTo`](prelude::SendTo) does this.
+    /// ```rust
+    /// use bevy::{ecs::system::RunSystemOnce, prelude::*};
+    /// use bevy::ecs::system::{lifetimeless::SRes, SystemParam};
+    /// use bevy_mod_sysfail::{prelude::*, Callsite, Level, WarnMissing};
+    ///
+    /// #[derive(Resource)]
+    /// struct Console;
+    ///
+    /// struct ToConsole(&'static str);
+    /// impl From<&'static str> for ToConsole {
+    ///     fn from(value: &'static str) -> Self {
+    ///         Self(value)
+    ///     }
+    /// }
+    /// impl Failure for ToConsole {
+    ///     type Param = (Option<SRes<Console>>, WarnMissing<'static>);
+    ///     const LEVEL: Level = Level::INFO;
+    ///
+    ///     fn handle_error(
+    ///         self,
+    ///         (console, mut warn): <Self::Param as SystemParam>::Item<'_, '_>,
+    ///         _: Option<&'static impl Callsite>,
+    ///     ) {
+    ///         match console {
+    ///             Some(_console) => println!("{}", self.0),
+    ///             None => warn.warn::<Console>("ToConsole"),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// #[sysfail(ToConsole)]
+    /// fn failable_system() {
+    ///     let () = Err("failed")?;
+    /// }
+    /// // Doesn't panic, logs a warning: "The `[…]::Console` resource used by
+    /// // `ToConsole` in the `[…]::failable_system` system doesn't exist, errors are discarded"
+    /// World::new().run_system_once(failable_system);
+    /// ```
     type Param: SystemParam;
 
     /// If this `Failure` logs something, use this log level.
@@ -67,12 +638,276 @@ pub trait Failure {
     /// `Callsite` at the macro invocation position and then pass it, otherwise
     /// the metadata for file and system position is all messed up.
     ///
-    /// Due to the overhead of creating a `Callsite`, **it is only
-    /// `Some` if the `Failure` type name contains the string `"Log"`**, such
-    /// as in `Log` or `LogSimply`.
+    /// The callsite is a `static`, its level is [`Self::LEVEL`]. Since `static`s
+    /// can't use the generic parameters of the system, **it is `None` when the
+    /// `Failure` type depends on the system's generics**, as in
+    /// `#[sysfail(MyFailure<T>)] fn system<T>()`, except for the logging
+    /// `Failure`s of this crate, which read their level from their `Lvl` type
+    /// parameter. It is also `None` when the `Failure` is called outside of
+    /// a `sysfail` system. The logging `Failure`s of this crate then log errors
+    /// with the target and location of this crate.
+    ///
+    /// The metadata of the callsite has two fields. The first one holds the
+    /// error message, it is named `"message"` unless the `field` option is used.
+    /// The second one, `"sysfail"`, is never recorded, it marks the event as
+    /// coming from a `sysfail` system, so that subscribers can filter them,
+    /// for example with the `[{sysfail}]` `EnvFilter` directive.
+    /// ```rust
+    /// use bevy::{ecs::system::RunSystemOnce, prelude::*};
+    /// use bevy_mod_sysfail::{Callsite, Level};
+    /// use bevy_mod_sysfail::prelude::*;
+    ///
+    /// struct CheckLog;
+    /// impl From<&'static str> for CheckLog {
+    ///     fn from(_: &'static str) -> Self {
+    ///         Self
+    ///     }
+    /// }
+    /// impl Failure for CheckLog {
+    ///     type Param = ();
+    ///     const LEVEL: Level = Level::WARN;
+    ///
+    ///     fn handle_error(self, (): (), callsite: Option<&'static impl Callsite>) {
+    ///         let fields = callsite.expect("no generics").metadata().fields();
+    ///         assert!(fields.field("message").is_some());
+    ///         assert!(fields.field("sysfail").is_some());
+    ///     }
+    /// }
+    ///
+    /// #[sysfail(CheckLog)]
+    /// fn failable_system() {
+    ///     let () = Err("failed")?;
+    /// }
+    /// World::new().run_system_once(failable_system);
+    /// ```
     fn handle_error(
         self,
         param: <Self::Param as SystemParam>::Item<'_, '_>,
         callsite: Option<&'static impl Callsite>,
     );
+
+    /// Like [`Self::handle_error`], but handling the error may itself fail.
+    ///
+    /// This is what `#[sysfail]` systems call. When this returns an `Err`,
+    /// it is logged at the error level. This fallback doesn't go through any
+    /// `Failure`, so it never fails or recurses.
+    ///
+    /// By default, this calls [`Self::handle_error`] and returns `Ok(())`.
+    /// Override it for `Failure`s that can fail, such as ones writing errors
+    /// to a file:
+    /// ```rust
+    /// use std::{fs::File, io::Write};
+    /// use bevy::prelude::*;
+    /// use bevy_mod_sysfail::{Callsite, Level};
+    /// use bevy_mod_sysfail::prelude::*;
+    ///
+    /// struct ToFile(String);
+    /// impl From<&'static str> for ToFile {
+    ///     fn from(value: &'static str) -> Self {
+    ///         Self(value.to_owned())
+    ///     }
+    /// }
+    /// impl Failure for ToFile {
+    ///     type Param = ();
+    ///     const LEVEL: Level = Level::ERROR;
+    ///
+    ///     fn handle_error(self, param: (), callsite: Option<&'static impl Callsite>) {
+    ///         // `#[sysfail]` doesn't call this, since we override `handle_error_fallible`.
+    ///         let _ = self.handle_error_fallible(param, callsite);
+    ///     }
+    ///     fn handle_error_fallible(
+    ///         self,
+    ///         (): (),
+    ///         _: Option<&'static impl Callsite>,
+    ///     ) -> Result<(), Box<dyn std::error::Error>> {
+    ///         let mut file = File::options().append(true).open("/not/a/file.log")?;
+    ///         writeln!(file, "{}", self.0)?;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// #[sysfail(ToFile)]
+    /// fn failable_system() {
+    ///     let () = Err("failed")?;
+    /// }
+    /// let mut app = App::new();
+    /// app.add_systems(Update, failable_system);
+    /// // Logs "Failed to handle the error of […]::failable_system: No such file or directory"
+    /// app.update();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// When handling the error failed.
+    fn handle_error_fallible(
+        self,
+        param: <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        Self: Sized,
+    {
+        self.handle_error(param, callsite);
+        Ok(())
+    }
+
+    /// Do something whenever a `#[sysfail]` system returns `Ok`.
+    ///
+    /// By default, this does nothing, and costs nothing. Use it to reset
+    /// state stored in [`Self::Param`], for example, to count how many times
+    /// in a row the system failed:
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy::ecs::system::{Local, SystemParam};
+    /// use bevy_mod_sysfail::{Callsite, Level};
+    /// use bevy_mod_sysfail::prelude::*;
+    ///
+    /// struct Backoff(&'static str);
+    /// impl From<&'static str> for Backoff {
+    ///     fn from(value: &'static str) -> Self {
+    ///         Self(value)
+    ///     }
+    /// }
+    /// impl Failure for Backoff {
+    ///     type Param = Local<'static, u32>;
+    ///     const LEVEL: Level = Level::WARN;
+    ///
+    ///     fn handle_error(self, mut failures: Local<u32>, _: Option<&'static impl Callsite>) {
+    ///         *failures += 1;
+    ///         if failures.is_power_of_two() {
+    ///             println!("{} (failed {} times in a row)", self.0, *failures);
+    ///         }
+    ///     }
+    ///     fn on_success(mut failures: Local<u32>) {
+    ///         *failures = 0;
+    ///     }
+    /// }
+    ///
+    /// #[sysfail(Backoff)]
+    /// fn failable_system(time: Res<Time>) {
+    ///     if time.elapsed_seconds() < 10.0 {
+    ///         let () = Err("Too early")?;
+    ///     }
+    /// }
+    /// ```
+    fn on_success(_param: <Self::Param as SystemParam>::Item<'_, '_>) {}
+
+    /// Setup the `World` for this `Failure`, for example, insert the
+    /// resources required by [`Self::Param`].
+    ///
+    /// This is called by `SysfailPlugin::with_failure`. By default, this
+    /// does nothing.
+    fn init(_world: &mut World) {}
+}
+
+/// The `Err` side of the return type of `#[exclusive_sysfail]`.
+///
+/// This is implemented for all [`Failure`]s with a `Param` of `()`, such as
+/// [`LogSimply`](prelude::LogSimply) or [`Ignore`](prelude::Ignore).
+/// Implement it to handle errors with full access to the `World`:
+/// ```rust
+/// use bevy::{ecs::system::RunSystemOnce, prelude::*};
+/// use bevy_mod_sysfail::{Callsite, ExclusiveFailure};
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct LastLevelError(String);
+///
+/// struct StoreInWorld(String);
+/// impl From<&'static str> for StoreInWorld {
+///     fn from(value: &'static str) -> Self {
+///         Self(value.to_owned())
+///     }
+/// }
+/// impl ExclusiveFailure for StoreInWorld {
+///     fn handle_exclusive(
+///         self,
+///         world: &mut World,
+///         _: Option<&'static impl Callsite>,
+///     ) -> Result<(), Box<dyn std::error::Error>> {
+///         world.insert_resource(LastLevelError(self.0));
+///         Ok(())
+///     }
+/// }
+///
+/// #[exclusive_sysfail(StoreInWorld)]
+/// fn load_level(world: &mut World) {
+///     let () = Err("Level not found")?;
+/// }
+/// let mut world = World::new();
+/// world.run_system_once(load_level);
+/// assert_eq!(world.resource::<LastLevelError>().0, "Level not found");
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be used as the error type of `#[exclusive_sysfail]`",
+    label = "not an `ExclusiveFailure`",
+    note = "only `Failure`s with a `StatelessParam`, such as `LogSimply`, can be used in exclusive systems, or types implementing `ExclusiveFailure`"
+)]
+pub trait ExclusiveFailure {
+    /// The level of the callsite passed to `handle_exclusive` when the
+    /// `Failure` is a generic parameter of the system, see
+    /// [generic systems](macro@sysfail#generic-systems).
+    ///
+    /// This is `Failure::LEVEL` for `Failure`s, `ERROR` by default.
+    const LEVEL: Level = Level::ERROR;
+
+    /// Do something whenever an `#[exclusive_sysfail]` system returns an `Err(Self)`.
+    ///
+    /// See [`Failure::handle_error`] for the `callsite` parameter.
+    ///
+    /// # Errors
+    ///
+    /// When handling the error failed, it is then logged at the error level.
+    fn handle_exclusive(
+        self,
+        world: &mut World,
+        callsite: Option<&'static impl Callsite>,
+    ) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Do something whenever an `#[exclusive_sysfail]` system returns `Ok`.
+    ///
+    /// By default, this does nothing.
+    fn on_success_exclusive(_world: &mut World) {}
+}
+
+impl<F: Failure> ExclusiveFailure for F
+where
+    F::Param: StatelessParam,
+{
+    const LEVEL: Level = F::LEVEL;
+
+    fn handle_exclusive(
+        self,
+        world: &mut World,
+        callsite: Option<&'static impl Callsite>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.handle_error_fallible(F::Param::from_world(world), callsite)
+    }
+
+    fn on_success_exclusive(world: &mut World) {
+        F::on_success(F::Param::from_world(world));
+    }
+}
+
+/// A [`Failure::Param`] built without running a system, from a `&World` or
+/// from nothing.
+///
+/// `Failure`s with such a `Param` can be used in `#[exclusive_sysfail]`
+/// systems and with `sysfail_handle!`. This is implemented for `()` and
+/// [`SysfailHooks`].
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be built outside of a regular system",
+    label = "not a `StatelessParam`",
+    note = "only `Failure`s with a `Param` of `()` or `SysfailHooks`, such as `LogSimply`, can be used in exclusive systems and with `sysfail_handle!`"
+)]
+pub trait StatelessParam: SystemParam {
+    /// The param of `#[exclusive_sysfail]` systems.
+    fn from_world(world: &World) -> Self::Item<'_, '_>;
+
+    /// The param of `sysfail_handle!`, which has no access to the `World`.
+    fn detached() -> Self::Item<'static, 'static>;
+}
+impl StatelessParam for () {
+    fn from_world(_: &World) {}
+    fn detached() {}
 }
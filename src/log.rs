@@ -1,14 +1,26 @@
-use std::{fmt, marker::PhantomData};
+use std::any::type_name;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::{fmt, marker::PhantomData, panic::Location};
 
 use bevy::time::Time;
-use bevy_ecs::system::{lifetimeless::SRes, Local, SystemParam};
-use bevy_utils::tracing::level_filters::{LevelFilter, STATIC_MAX_LEVEL};
-use bevy_utils::{Duration, HashMap};
+use bevy_ecs::system::{lifetimeless::SRes, lifetimeless::SResMut, Local, Resource, SystemParam};
+use bevy_ecs::world::World;
+use bevy_utils::tracing::{trace, warn, Metadata};
+use bevy_utils::{hashbrown::HashMap, Duration, FixedState, PassHash};
 
-use crate::{log_levels::Warn, Callsite, Dedup, Failure, Level, LogLevelModifier};
+use crate::{
+    context::cfg_test,
+    dispatch::dispatch_message,
+    log_levels::{LevelN, Warn},
+    Callsite, Dedup, DedupFailure, Failure, Level, LogLevelModifier, SysfailConfig, SysfailHooks,
+    SysfailLevelOverrides, SysfailObserver,
+};
 
 /// Log `T`.
 ///
+/// This requires the `full` feature, enabled by default. Without it, use
+/// [`LogSimply`](crate::prelude::LogSimply).
+///
 /// # Example
 ///
 /// This will log `MyError` using [`Warn`]:
@@ -39,40 +51,828 @@ use crate::{log_levels::Warn, Callsite, Dedup, Failure, Level, LogLevelModifier}
 /// }
 /// ```
 /// Available as second argument are `Trace`, `Debug`, `Info`, `Warn`, `Error`.
-pub struct Log<T, Lvl = Warn>(pub T, PhantomData<Lvl>);
+///
+/// # Spans
+///
+/// Errors are logged as children of the current tracing span, so the fields
+/// of the span in which the system runs (such as the system span added
+/// by bevy with the `trace` feature) show up in the log. This is also true of
+/// [`LogSimply`](crate::prelude::LogSimply).
+///
+/// # Subscribers
+///
+/// Errors are sent to the global tracing subscriber, such as the one of
+/// bevy's `LogPlugin`, and follow its filters. Custom layers added with
+/// `LogPlugin::update_subscriber`, for example for an in-game console, see
+/// them like any other event. This is also true of the other logging
+/// `Failure`s of this crate.
+/// ```rust
+/// use std::sync::Mutex;
+/// use bevy::log::tracing_subscriber::{layer::Context, prelude::*, Layer};
+/// use bevy::log::{BoxedSubscriber, LogPlugin};
+/// use bevy::prelude::*;
+/// use bevy::utils::tracing::{Event, Subscriber};
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// static CONSOLE: Mutex<Vec<String>> = Mutex::new(Vec::new());
+///
+/// struct ConsoleLayer;
+/// impl<S: Subscriber> Layer<S> for ConsoleLayer {
+///     fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+///         let meta = event.metadata();
+///         if meta.fields().field("sysfail").is_some() {
+///             CONSOLE.lock().unwrap().push(meta.target().to_owned());
+///         }
+///     }
+/// }
+/// fn add_console(subscriber: BoxedSubscriber) -> BoxedSubscriber {
+///     Box::new(subscriber.with(ConsoleLayer))
+/// }
+///
+/// #[sysfail(Log<&'static str>, target = "shown")]
+/// fn shown_system() {
+///     let () = Err("Shown in the console")?;
+/// }
+/// #[sysfail(Log<&'static str>, target = "quiet")]
+/// fn quiet_system() {
+///     let () = Err("Filtered out by LogPlugin")?;
+/// }
+/// let log_plugin = LogPlugin {
+///     filter: "quiet=error".to_owned(),
+///     update_subscriber: Some(add_console),
+///     ..default()
+/// };
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, log_plugin))
+///     .add_systems(Update, (shown_system, quiet_system));
+/// app.update();
+/// assert_eq!(*CONSOLE.lock().unwrap(), ["shown"]);
+/// ```
+///
+/// # Cooldown clock
+///
+/// The [`Dedup::cooldown`] is measured using [`Time::elapsed`] of the
+/// `Time<Clock>` resource. By default, this is `Time<()>`, the "default" bevy
+/// clock: In `Update`, it follows `Time<Virtual>`, in `FixedUpdate`, it follows
+/// `Time<Fixed>`.
+///
+/// This means that when virtual time is paused, the cooldown is paused as well,
+/// and errors repeated while paused will not show up until time resumes.
+/// Use `Time<Real>` as the third type parameter to measure cooldowns in
+/// wall clock time instead:
+/// ```rust
+/// use bevy_mod_sysfail::prelude::*;
+/// use bevy::prelude::*;
+/// # type MyError = &'static str;
+/// # const MyError: &'static str = "FOOBAR";
+///
+/// #[sysfail(Log<MyError, Warn, Real>)]
+/// fn failable_system(q: Query<&Transform>) {
+///     let () = Err(MyError)?;
+///     // ...
+/// }
+/// ```
+///
+/// If the `Time<Clock>` resource doesn't exist, for example in a minimal app
+/// without the `TimePlugin`, `Log` doesn't deduplicate errors and logs every
+/// time, like [`LogSimply`](crate::prelude::LogSimply).
+/// ```rust
+/// use bevy_mod_sysfail::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[sysfail(Log<&'static str>)]
+/// fn failable_system() {
+///     let () = Err("No time here")?;
+/// }
+/// let mut app = App::new();
+/// app.add_systems(Update, failable_system);
+/// // Doesn't panic, even if there is no `Time` resource
+/// app.update();
+/// ```
+///
+/// # Suppression notice
+///
+/// The first time an error is suppressed after being shown, `Log` emits
+/// a single [`Level::TRACE`] event with the [`Dedup::cooldown`]: errors with
+/// the same [`Dedup::identify`] ID are suppressed until none occur for that
+/// long. It is not repeated until the error is shown again, so enabling
+/// `TRACE` doesn't flood the logs.
+///
+/// # Hasher
+///
+/// `Log` only stores the hash of the [`Dedup::identify`] ID of errors. The
+/// fourth type parameter is the [`BuildHasher`] computing this hash, by
+/// default the same fixed aHash bevy uses. See [`LogWith`] to change it,
+/// for example to hash large IDs with a faster hasher.
+///
+/// Only `Log` and `LogWith` accept a custom hasher. The other variants,
+/// [`LogPerSite`], [`LogRedacted`], [`LogVerbose`] and [`LogShared`], always
+/// use the default one.
+pub struct Log<T, Lvl = Warn, Clock = (), S = FixedState>(pub T, PhantomData<(Lvl, Clock, S)>);
+
+/// [`Log`] the error, then panic in tests.
+///
+/// When the system is defined in a crate compiled with `cfg(test)`, such as
+/// unit tests or integration tests, the system panics after logging the
+/// error, so that the test fails. The panic happens on every error, even when
+/// `Log` suppressed it because of the [`Dedup::cooldown`].
+///
+/// Otherwise, for example in `cargo run` or in release builds, this is the
+/// same as `Log`, and never panics. Nothing is logged and the system doesn't
+/// panic when [`SysfailEnabled`] is `false`.
+///
+/// `cfg!(test)` is read by `#[sysfail]` when handling the error, so
+/// `LogOrPanic` doesn't panic when the error is handled later, as with
+/// [`Defer`](crate::prelude::Defer).
+///
+/// [`SysfailEnabled`]: crate::SysfailEnabled
+///
+/// # Example
+///
+/// Doc tests are not compiled with `cfg(test)`, so this only logs:
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, testing::run_and_capture, Level};
+///
+/// #[sysfail(LogOrPanic<&'static str>)]
+/// fn failable_system(q: Query<&Transform>) {
+///     let () = Err("Fails the tests")?;
+/// }
+/// let mut world = World::new();
+/// let logs = run_and_capture(&mut world, failable_system);
+/// assert_eq!(logs, [(Level::WARN, "Fails the tests".to_owned())]);
+/// ```
+pub struct LogOrPanic<T, Lvl = Warn, Clock = (), S = FixedState>(
+    pub T,
+    PhantomData<(Lvl, Clock, S)>,
+);
+
+/// [`Log`] the error with a numeric level, see [`LevelN`].
+///
+/// `LEVEL` goes from `0` for `TRACE` to `4` for `ERROR`, it can be computed
+/// in a `const` context.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, testing::run_and_capture, Level};
+///
+/// const fn level_from_env() -> u8 {
+///     match option_env!("MY_GAME_ERROR_LEVEL") {
+///         Some(_) => 4,
+///         None => 2,
+///     }
+/// }
+/// #[sysfail(LogN<&'static str, { level_from_env() }>)]
+/// fn failable_system() {
+///     let () = Err("Logged at the level from the environment")?;
+/// }
+/// let logs = run_and_capture(&mut World::new(), failable_system);
+/// assert_eq!(logs[0].0, Level::INFO);
+/// ```
+///
+/// [`LevelN`]: crate::prelude::LevelN
+pub type LogN<T, const LEVEL: u8, Clock = ()> = Log<T, LevelN<LEVEL>, Clock>;
 
-impl<U: From<T>, T: fmt::Debug, L> From<T> for Log<U, L> {
+/// [`Log`] the error, hashing the [`Dedup::identify`] IDs with `S`.
+///
+/// This is `Log<T, Lvl, Clock, S>`, with the hasher before the clock, since
+/// it is more often changed. The hasher is built once per system, so it can
+/// be a randomly seeded, DoS-resistant, hasher, such as
+/// [`RandomState`](std::collections::hash_map::RandomState), useful when the
+/// IDs come from untrusted sources, like network clients. For large IDs,
+/// a faster non-cryptographic hasher can be used instead.
+///
+/// # Example
+///
+/// ```rust
+/// use std::collections::hash_map::RandomState;
+/// use bevy_mod_sysfail::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[sysfail(LogWith<String, Warn, RandomState>)]
+/// fn failable_system() {
+///     let () = Err(format!("Invalid packet from client {}", 42))?;
+/// }
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins).add_systems(Update, failable_system);
+/// app.update();
+/// ```
+pub type LogWith<T, Lvl, S, Clock = ()> = Log<T, Lvl, Clock, S>;
+
+impl<U: From<T>, T: fmt::Debug, L, C, S> From<T> for Log<U, L, C, S> {
     fn from(t: T) -> Self {
         Self(t.into(), PhantomData)
     }
 }
 
-impl<T: Dedup, Lvl: LogLevelModifier> Failure for Log<T, Lvl> {
-    type Param = (SRes<Time>, Local<'static, HashMap<T::ID, Duration>>);
+/// The [`Failure::Param`] of [`Log`] and its variants, such as [`LogVerbose`].
+type LogParam<Clock, S = FixedState> = (
+    Option<SRes<Time<Clock>>>,
+    Local<'static, LastShown<S>>,
+    Option<SRes<SysfailObserver>>,
+    Option<SRes<SysfailConfig>>,
+    Option<SRes<SysfailLevelOverrides>>,
+    SysfailHooks<'static>,
+);
+
+/// The [`Shown`] state of errors, keyed by the hash of the [`Dedup::identify`]
+/// ID, computed with `S`.
+pub struct LastShown<S = FixedState> {
+    hasher: S,
+    shown: HashMap<u64, Shown, PassHash>,
+}
+impl<S: Default> Default for LastShown<S> {
+    fn default() -> Self {
+        Self { hasher: S::default(), shown: HashMap::default() }
+    }
+}
+
+/// The dedup state of an error.
+#[derive(Clone, Copy)]
+pub struct Shown {
+    /// When the error was last handled.
+    last: Duration,
+    /// Whether the suppression of the error was announced since it was last
+    /// shown.
+    announced: bool,
+}
+
+/// The resources used by [`log_deduped`].
+type DedupParam<'a, Clock, S> = (
+    Option<&'a Time<Clock>>,
+    Option<&'a mut LastShown<S>>,
+    Option<&'a SysfailObserver>,
+    Option<&'a SysfailConfig>,
+    Option<&'a SysfailLevelOverrides>,
+    SysfailHooks<'a>,
+);
+
+/// Log `message` at `level`, unless `error` was recently logged.
+///
+/// `logged` is `None` when errors should not be deduplicated. Errors are not
+/// deduplicated either when [`SysfailConfig::dedup_enabled`] is `false`.
+///
+/// Nothing is logged, recorded or observed when `hooks` is disabled.
+fn log_deduped<T: Dedup, Clock: Default + Send + Sync + 'static, S: BuildHasher>(
+    error: &T,
+    message: fmt::Arguments,
+    level: Level,
+    (time, logged, observer, config, overrides, hooks): DedupParam<Clock, S>,
+    callsite: Option<&'static impl Callsite>,
+) {
+    if !hooks.enabled() {
+        return;
+    }
+    let logged = logged.filter(|_| config.map_or(true, |config| config.dedup_enabled));
+    let mut suppressed = None;
+    // Without `Time`, we can't dedup, so we always log, like `LogSimply`.
+    let should_log = time.zip(logged).map_or(true, |(time, logged)| {
+        let cooldown = error.cooldown();
+        let now = time.elapsed();
+        let mut hasher = logged.hasher.build_hasher();
+        error.id_hash(&mut hasher);
+        let id = hasher.finish();
+        let Some(shown) = logged.shown.get_mut(&id) else {
+            logged
+                .shown
+                .insert(id, Shown { last: now, announced: false });
+            return true;
+        };
+        let should_log = now >= shown.last + cooldown;
+        if !should_log && !shown.announced {
+            suppressed = Some(cooldown);
+        }
+        *shown = Shown { last: now, announced: !should_log };
+        should_log
+    });
+    let meta = callsite.map(|callsite| callsite.metadata());
+    let target = meta.map_or("unknown", Metadata::target);
+    if let Some(cooldown) = suppressed {
+        trace!("Suppressing identical errors of {target} until none occur for {cooldown:?}");
+    }
+    if let Some(observer) = observer {
+        observer.on_error(target, should_log);
+    }
+    if should_log {
+        hooks.count(level);
+    }
+    let filter = overrides.and_then(|overrides| overrides.get(target));
+    if should_log {
+        dispatch_message(meta, level, filter, message);
+    }
+}
+
+/// Call [`log_deduped`] with the [`LogParam`] of a `Failure`.
+fn log_with_param<T, Clock, S>(
+    error: &T,
+    message: fmt::Arguments,
+    level: Level,
+    param: <LogParam<Clock, S> as SystemParam>::Item<'_, '_>,
+    callsite: Option<&'static impl Callsite>,
+) where
+    T: Dedup,
+    Clock: Default + Send + Sync + 'static,
+    S: BuildHasher + Default + Send + Sync + 'static,
+{
+    let (time, mut logged, observer, config, overrides, hooks) = param;
+    let param = (
+        time.as_deref(),
+        Some(&mut *logged),
+        observer.as_deref(),
+        config.as_deref(),
+        overrides.as_deref(),
+        hooks,
+    );
+    log_deduped::<T, Clock, S>(error, message, level, param, callsite);
+}
+
+impl<T, Lvl, Clock, S> Failure for Log<T, Lvl, Clock, S>
+where
+    T: Dedup,
+    Lvl: LogLevelModifier,
+    Clock: Default + Send + Sync + 'static,
+    S: BuildHasher + Default + Send + Sync + 'static,
+{
+    type Param = LogParam<Clock, S>;
 
     const LEVEL: Level = Lvl::LEVEL;
 
     fn handle_error(
         self,
-        (time, mut logged): <Self::Param as SystemParam>::Item<'_, '_>,
+        param: <Self::Param as SystemParam>::Item<'_, '_>,
         callsite: Option<&'static impl Callsite>,
     ) {
-        let cooldown = self.0.cooldown();
-        let now = time.elapsed();
-        let last_shown = logged.insert(self.0.identify(), now);
-        let should_log = last_shown.map_or(true, |d| now < d + cooldown);
-        if should_log {
-            let meta = callsite.unwrap().metadata();
-            if Lvl::LEVEL <= STATIC_MAX_LEVEL && Lvl::LEVEL <= LevelFilter::current() {
-                let mut iter = meta.fields().iter();
-                bevy_utils::tracing::Event::dispatch(
-                    meta,
-                    &meta.fields().value_set(&[(
-                        &(iter.next().expect("FieldSet corrupted (this is a bug)")),
-                        Some(&format_args!("{}", self.0) as &dyn bevy_utils::tracing::field::Value),
-                    )]),
-                );
-            }
+        log_with_param(
+            &self.0,
+            format_args!("{}", self.0),
+            Lvl::LEVEL,
+            param,
+            callsite,
+        );
+    }
+}
+
+impl<T, Lvl, Clock, S> DedupFailure for Log<T, Lvl, Clock, S>
+where
+    T: Dedup,
+    Lvl: LogLevelModifier,
+    Clock: Default + Send + Sync + 'static,
+    S: BuildHasher + Default + Send + Sync + 'static,
+{
+    type Error = T;
+
+    fn error(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<U: From<T>, T: fmt::Debug, L, C, S> From<T> for LogOrPanic<U, L, C, S> {
+    fn from(t: T) -> Self {
+        Self(t.into(), PhantomData)
+    }
+}
+
+impl<T, Lvl, Clock, S> Failure for LogOrPanic<T, Lvl, Clock, S>
+where
+    T: Dedup,
+    Lvl: LogLevelModifier,
+    Clock: Default + Send + Sync + 'static,
+    S: BuildHasher + Default + Send + Sync + 'static,
+{
+    type Param = LogParam<Clock, S>;
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(
+        self,
+        param: <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        let (.., hooks) = &param;
+        let enabled = hooks.enabled();
+        log_with_param(
+            &self.0,
+            format_args!("{}", self.0),
+            Lvl::LEVEL,
+            param,
+            callsite,
+        );
+        let should_panic = enabled && cfg_test();
+        assert!(!should_panic, "A LogOrPanic system failed: {}", self.0);
+    }
+}
+
+/// Like [`Log`], but errors are deduplicated separately for each `?` in the
+/// system.
+///
+/// The error ID is the [`Dedup::identify`] ID of `T` together with the source
+/// location where the error was converted into `LogPerSite`, usually the `?`
+/// operator. This is useful for errors such as `anyhow::Error`, where all
+/// errors have the same ID, and a system can fail at several places.
+///
+/// The conversion is `#[track_caller]`, so in a `#[track_caller]` function
+/// returning a `LogPerSite` error, the location is the caller's location.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::Mutex;
+/// use bevy::prelude::*;
+/// use bevy::ecs::schedule::ExecutorKind;
+/// use bevy_mod_sysfail::{prelude::*, testing::capture_logs};
+///
+/// static LINES: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+/// fn record_line(error: &LogPerSite<anyhow::Error>) {
+///     LINES.lock().unwrap().push(error.location().line());
+/// }
+///
+/// #[sysfail(LogPerSite<anyhow::Error>, on_error = record_line)]
+/// fn two_fail_points(mut runs: Local<u32>) {
+///     *runs += 1;
+///     if *runs % 2 == 1 {
+///         let () = Err(anyhow::anyhow!("First failed"))?;
+///     }
+///     let () = Err(anyhow::anyhow!("Second failed"))?;
+/// }
+/// let mut world = World::new();
+/// world.init_resource::<Time>();
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems(two_fail_points);
+/// let logs = capture_logs(|| {
+///     schedule.run(&mut world);
+///     schedule.run(&mut world);
+/// });
+/// // With `Log<anyhow::Error>`, the second error would be deduplicated
+/// assert_eq!(logs[0].1, "First failed");
+/// assert_eq!(logs[1].1, "Second failed");
+/// let lines = LINES.lock().unwrap();
+/// assert_ne!(lines[0], lines[1]);
+/// ```
+pub struct LogPerSite<T, Lvl = Warn, Clock = ()>(
+    pub T,
+    &'static Location<'static>,
+    PhantomData<(Lvl, Clock)>,
+);
+
+impl<T, Lvl, Clock> LogPerSite<T, Lvl, Clock> {
+    /// Where the error was converted into `LogPerSite`.
+    #[must_use]
+    pub const fn location(&self) -> &'static Location<'static> {
+        self.1
+    }
+}
+
+impl<U: From<T>, T: fmt::Debug, L, C> From<T> for LogPerSite<U, L, C> {
+    #[track_caller]
+    fn from(t: T) -> Self {
+        Self(t.into(), Location::caller(), PhantomData)
+    }
+}
+
+/// An error at a given source location, deduplicated by both.
+struct AtSite<'a, T>(&'a T, &'static Location<'static>);
+
+impl<T: fmt::Display> fmt::Display for AtSite<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+impl<T: Dedup> Dedup for AtSite<'_, T> {
+    type ID = (T::ID, &'static Location<'static>);
+
+    fn cooldown(&self) -> Duration {
+        self.0.cooldown()
+    }
+    fn identify(&self) -> Self::ID {
+        (self.0.identify(), self.1)
+    }
+    fn id_hash(&self, hasher: &mut impl Hasher) {
+        self.0.id_hash(hasher);
+        self.1.hash(hasher);
+    }
+}
+
+impl<T, Lvl, Clock> Failure for LogPerSite<T, Lvl, Clock>
+where
+    T: Dedup,
+    Lvl: LogLevelModifier,
+    Clock: Default + Send + Sync + 'static,
+{
+    type Param = LogParam<Clock>;
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(
+        self,
+        param: <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        let error = AtSite(&self.0, self.1);
+        log_with_param(
+            &error,
+            format_args!("{}", self.0),
+            Lvl::LEVEL,
+            param,
+            callsite,
+        );
+    }
+}
+
+/// An error with a redacted message, for [`LogRedacted`].
+pub trait Redact {
+    /// The message to log in place of the [`Display`](fmt::Display) of the
+    /// error, for example without sensitive details.
+    fn redact(&self) -> String;
+}
+
+/// Like [`Log`], but log the [`Redact::redact`] message.
+///
+/// The original error is still used for deduplication, and is available
+/// before redaction with the `on_error` option of `#[sysfail]`, for example
+/// to collect metrics. Deduplication and log levels work exactly like with `Log`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::fmt;
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, testing::run_and_capture, Dedup, Redact};
+///
+/// #[derive(Debug)]
+/// struct LoginFailed { user: String, password: String }
+/// impl fmt::Display for LoginFailed {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "Login failed for {}:{}", self.user, self.password)
+///     }
+/// }
+/// impl Dedup for LoginFailed {
+///     type ID = String;
+///     fn identify(&self) -> String {
+///         self.user.clone()
+///     }
+/// }
+/// impl Redact for LoginFailed {
+///     fn redact(&self) -> String {
+///         format!("Login failed for {}", self.user)
+///     }
+/// }
+///
+/// #[sysfail(LogRedacted<LoginFailed>)]
+/// fn login() {
+///     let (user, password) = ("ferris".to_owned(), "hunter2".to_owned());
+///     let () = Err(LoginFailed { user, password })?;
+/// }
+/// let logs = run_and_capture(&mut World::new(), login);
+/// assert_eq!(logs[0].1, "Login failed for ferris");
+/// ```
+pub struct LogRedacted<T, Lvl = Warn, Clock = ()>(pub T, PhantomData<(Lvl, Clock)>);
+
+impl<U: From<T>, T: fmt::Debug, L, C> From<T> for LogRedacted<U, L, C> {
+    fn from(t: T) -> Self {
+        Self(t.into(), PhantomData)
+    }
+}
+
+impl<T, Lvl, Clock> Failure for LogRedacted<T, Lvl, Clock>
+where
+    T: Dedup + Redact,
+    Lvl: LogLevelModifier,
+    Clock: Default + Send + Sync + 'static,
+{
+    type Param = LogParam<Clock>;
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(
+        self,
+        param: <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        log_with_param(
+            &self.0,
+            format_args!("{}", self.0.redact()),
+            Lvl::LEVEL,
+            param,
+            callsite,
+        );
+    }
+}
+
+impl<T, Lvl, Clock> DedupFailure for LogRedacted<T, Lvl, Clock>
+where
+    T: Dedup + Redact,
+    Lvl: LogLevelModifier,
+    Clock: Default + Send + Sync + 'static,
+{
+    type Error = T;
+
+    fn error(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Like [`Log`], but log the detailed [`Dedup::fmt_verbose`] message.
+///
+/// For `anyhow::Error` and `eyre::Report` (with the `eyre` feature), this
+/// prints the error with `{:?}`, which includes the chain of causes and the
+/// backtrace, if enabled. For
+/// `Box<dyn std::error::Error>`, this prints the chain of `source`s.
+/// Other types use [`Display`](fmt::Display) unless they override `fmt_verbose`.
+///
+/// Deduplication and log levels work exactly like with `Log`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_mod_sysfail::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[sysfail(LogVerbose<anyhow::Error, Error>)]
+/// fn failable_system() {
+///     let () = Err(anyhow::anyhow!("root cause")).map_err(|e| e.context("Failed"))?;
+/// }
+/// ```
+pub struct LogVerbose<T, Lvl = Warn, Clock = ()>(pub T, PhantomData<(Lvl, Clock)>);
+
+impl<U: From<T>, T: fmt::Debug, L, C> From<T> for LogVerbose<U, L, C> {
+    fn from(t: T) -> Self {
+        Self(t.into(), PhantomData)
+    }
+}
+
+/// Display `T` using [`Dedup::fmt_verbose`].
+struct Verbose<'a, T>(&'a T);
+
+impl<T: Dedup> fmt::Display for Verbose<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_verbose(f)
+    }
+}
+
+impl<T, Lvl, Clock> Failure for LogVerbose<T, Lvl, Clock>
+where
+    T: Dedup,
+    Lvl: LogLevelModifier,
+    Clock: Default + Send + Sync + 'static,
+{
+    type Param = LogParam<Clock>;
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(
+        self,
+        param: <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        log_with_param(
+            &self.0,
+            format_args!("{}", Verbose(&self.0)),
+            Lvl::LEVEL,
+            param,
+            callsite,
+        );
+    }
+}
+
+impl<T, Lvl, Clock> DedupFailure for LogVerbose<T, Lvl, Clock>
+where
+    T: Dedup,
+    Lvl: LogLevelModifier,
+    Clock: Default + Send + Sync + 'static,
+{
+    type Error = T;
+
+    fn error(&self) -> &T {
+        &self.0
+    }
+}
+
+/// The app-wide deduplication state of [`LogShared<T>`].
+///
+/// Insert it with `app.init_resource::<SharedDedup<T>>()`, or with
+/// `SysfailPlugin::default().with_failure::<LogShared<T>>()`.
+#[derive(Resource)]
+pub struct SharedDedup<T> {
+    logged: LastShown,
+    _error: PhantomData<fn() -> T>,
+}
+impl<T> Default for SharedDedup<T> {
+    fn default() -> Self {
+        Self { logged: LastShown::default(), _error: PhantomData }
+    }
+}
+
+/// Like [`Log`], but the errors of all systems using `LogShared<T>` are
+/// deduplicated together.
+///
+/// With `Log`, each system keeps track of its own errors, so the same error
+/// returned by two systems is logged by both. With `LogShared<T>`, it is
+/// only logged once per [`Dedup::cooldown`].
+///
+/// The cooldowns are stored in the [`SharedDedup<T>`] resource, keyed by the
+/// [`Dedup::identify`] ID. Since the resource is accessed mutably, systems
+/// using the same `LogShared<T>` do not run in parallel.
+///
+/// If `SharedDedup<T>` doesn't exist, errors are not deduplicated, and a
+/// warning telling to add `SysfailPlugin::with_shared_dedup::<T>()` is logged
+/// once per system.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_mod_sysfail::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[sysfail(LogShared<&'static str>)]
+/// fn first_system() {
+///     let () = Err("Shared error")?;
+/// }
+/// #[sysfail(LogShared<&'static str>)]
+/// fn second_system() {
+///     // Not logged, since `first_system` logged it already.
+///     let () = Err("Shared error")?;
+/// }
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .init_resource::<SharedDedup<&'static str>>()
+///     .add_systems(Update, (first_system, second_system).chain());
+/// app.update();
+/// ```
+pub struct LogShared<T, Lvl = Warn, Clock = ()>(pub T, PhantomData<(Lvl, Clock)>);
+
+impl<U: From<T>, T: fmt::Debug, L, C> From<T> for LogShared<U, L, C> {
+    fn from(t: T) -> Self {
+        Self(t.into(), PhantomData)
+    }
+}
+
+impl<T, Lvl, Clock> Failure for LogShared<T, Lvl, Clock>
+where
+    T: Dedup + 'static,
+    Lvl: LogLevelModifier,
+    Clock: Default + Send + Sync + 'static,
+{
+    type Param = (
+        Option<SRes<Time<Clock>>>,
+        Option<SResMut<SharedDedup<T>>>,
+        Option<SRes<SysfailObserver>>,
+        Option<SRes<SysfailConfig>>,
+        Option<SRes<SysfailLevelOverrides>>,
+        Local<'static, bool>,
+        SysfailHooks<'static>,
+    );
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(
+        self,
+        param: <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        let (time, shared, observer, config, overrides, mut warned, hooks) = param;
+        if shared.is_none() && !*warned {
+            *warned = true;
+            warn!(
+                "The `SharedDedup<{error}>` resource used by `LogShared` doesn't exist, errors \
+                are not deduplicated. Add `SysfailPlugin::with_shared_dedup::<{error}>()`.",
+                error = type_name::<T>(),
+            );
         }
+        let logged = shared.map(|shared| &mut shared.into_inner().logged);
+        let param = (
+            time.as_deref(),
+            logged,
+            observer.as_deref(),
+            config.as_deref(),
+            overrides.as_deref(),
+            hooks,
+        );
+        log_deduped::<T, Clock, _>(
+            &self.0,
+            format_args!("{}", self.0),
+            Lvl::LEVEL,
+            param,
+            callsite,
+        );
+    }
+
+    fn init(world: &mut World) {
+        world.init_resource::<SharedDedup<T>>();
+    }
+}
+
+impl<T, Lvl, Clock> DedupFailure for LogShared<T, Lvl, Clock>
+where
+    T: Dedup + 'static,
+    Lvl: LogLevelModifier,
+    Clock: Default + Send + Sync + 'static,
+{
+    type Error = T;
+
+    fn error(&self) -> &T {
+        &self.0
     }
 }
@@ -0,0 +1,104 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fmt, marker::PhantomData};
+
+use bevy_utils::tracing::level_filters::{LevelFilter, STATIC_MAX_LEVEL};
+use bevy_utils::tracing::Metadata;
+
+use crate::context::with_context_fields;
+use crate::dispatch::{dispatch, dispatch_fallback, is_enabled};
+use crate::{log_levels::Warn, Callsite, Failure, Level, LogLevelModifier, SysfailHooks};
+
+/// Log `T` as a single line JSON object, for machine ingestion.
+///
+/// The object has the following fields:
+///
+/// - `ts`: The time of the error, in seconds since the UNIX epoch.
+/// - `level`: The level of the error, such as `"WARN"`.
+/// - `target`, `file` and `line`: Where the faillible system is defined.
+/// - `error`: The `Display` message of the error.
+/// - `context`: The values recorded with `sysfail_context!`, as an object of
+///   strings. Only present when the system recorded context.
+///
+/// Like [`LogSimply`](crate::prelude::LogSimply), there is no deduplication,
+/// and the level is used to filter logs.
+///
+/// This requires the `json` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, testing::run_and_capture};
+///
+/// #[sysfail(LogJson<&'static str, Error>)]
+/// fn failable_system() {
+///     let () = Err("\"quoted\" error")?;
+/// }
+/// let logs = run_and_capture(&mut World::new(), failable_system);
+/// let json: serde_json::Value = serde_json::from_str(&logs[0].1).unwrap();
+/// assert_eq!(json["level"], "ERROR");
+/// assert_eq!(json["error"], "\"quoted\" error");
+///
+/// #[sysfail(LogJson<&'static str>)]
+/// fn check_names(query: Query<&Name>) {
+///     for (i, name) in query.iter().enumerate() {
+///         sysfail_context!("iteration" => i);
+///         if name.is_empty() {
+///             let () = Err("Empty name")?;
+///         }
+///     }
+/// }
+/// let mut world = World::new();
+/// world.spawn_batch([Name::new("Joe"), Name::new("")]);
+/// let logs = run_and_capture(&mut world, check_names);
+/// let json: serde_json::Value = serde_json::from_str(&logs[0].1).unwrap();
+/// assert_eq!(json["error"], "Empty name");
+/// assert_eq!(json["context"]["iteration"], "1");
+/// ```
+pub struct LogJson<T, Lvl = Warn>(pub T, PhantomData<Lvl>);
+
+impl<U: From<T>, T: fmt::Debug, L> From<T> for LogJson<U, L> {
+    fn from(t: T) -> Self {
+        Self(t.into(), PhantomData)
+    }
+}
+
+impl<T: fmt::Display, Lvl: LogLevelModifier> Failure for LogJson<T, Lvl> {
+    type Param = SysfailHooks<'static>;
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(self, hooks: SysfailHooks, callsite: Option<&'static impl Callsite>) {
+        if !hooks.enabled() {
+            return;
+        }
+        let meta = callsite.map(|callsite| callsite.metadata());
+        hooks.count(Lvl::LEVEL);
+        let enabled = Lvl::LEVEL <= STATIC_MAX_LEVEL && Lvl::LEVEL <= LevelFilter::current();
+        if enabled && meta.map_or(true, is_enabled) {
+            let ts = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let mut json = serde_json::json!({
+                "ts": ts.as_secs_f64(),
+                "level": Lvl::LEVEL.as_str(),
+                "target": meta.map(Metadata::target),
+                "file": meta.and_then(Metadata::file),
+                "line": meta.and_then(Metadata::line),
+                "error": self.0.to_string(),
+            });
+            with_context_fields(|context| {
+                if !context.is_empty() {
+                    let context = context
+                        .iter()
+                        .map(|(k, v)| ((*k).to_owned(), v.as_str().into()));
+                    json["context"] = serde_json::Value::Object(context.collect());
+                }
+            });
+            match meta {
+                Some(meta) => dispatch(meta, format_args!("{json}"), &[]),
+                None => dispatch_fallback(Lvl::LEVEL, format_args!("{json}")),
+            }
+        }
+    }
+}
@@ -28,3 +28,101 @@ impl_loglevel_modifier![
     /// Log with the `ERROR` level, this is similar to `error!`.
     Error => ERROR,
 ];
+
+/// The [`Level`] for `level`, from `0` for `TRACE` to `4` for `ERROR`.
+///
+/// This is a `const fn`, to compute the level of a [`LevelN`] in a `const`
+/// context.
+///
+/// # Panics
+///
+/// When `level` is greater than `4`. This is a compile error in `const`
+/// contexts.
+///
+/// ```rust
+/// use bevy_mod_sysfail::{u8_to_level, Level};
+///
+/// const LEVEL: Level = u8_to_level(3);
+/// assert_eq!(LEVEL, Level::WARN);
+/// ```
+#[must_use]
+pub const fn u8_to_level(level: u8) -> Level {
+    match level {
+        0 => Level::TRACE,
+        1 => Level::DEBUG,
+        2 => Level::INFO,
+        3 => Level::WARN,
+        4 => Level::ERROR,
+        _ => panic!("log levels go from 0 (TRACE) to 4 (ERROR)"),
+    }
+}
+
+/// Log with the level `N`, as converted by [`u8_to_level`].
+///
+/// This is a [`LogLevelModifier`] like the others, so it can be used with
+/// any `Failure` accepting one. `N` can be computed by a `const` expression,
+/// for example from an environment variable read at build time. Out of range
+/// values fail to compile.
+///
+/// ```rust,compile_fail
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail(LogSimply<&'static str, LevelN<5>>)]
+/// fn failable_system() {
+///     let () = Err("There is no level 5")?;
+/// }
+/// ```
+pub struct LevelN<const N: u8>;
+impl<const N: u8> LogLevelModifier for LevelN<N> {
+    const LEVEL: Level = u8_to_level(N);
+}
+
+/// The [`Level`] named `name`, ignoring case, or `None` if it isn't a level.
+///
+/// This accepts the level names, such as `"warn"` or `"ERROR"`, and the
+/// `"warning"` and `"err"` aliases. Use it to read levels from configuration
+/// files, for example to build the `LevelFilter`s of `SysfailLevelOverrides`
+/// with `LevelFilter::from_level`.
+///
+/// ```rust
+/// use bevy_mod_sysfail::{parse_level, Level};
+///
+/// assert_eq!(parse_level("warn"), Some(Level::WARN));
+/// assert_eq!(parse_level("Warning"), Some(Level::WARN));
+/// assert_eq!(parse_level("ERR"), Some(Level::ERROR));
+/// assert_eq!(parse_level("trace"), Some(Level::TRACE));
+/// assert_eq!(parse_level("loud"), None);
+/// ```
+#[must_use]
+pub fn parse_level(name: &str) -> Option<Level> {
+    let is = |level: &str| name.eq_ignore_ascii_case(level);
+    match () {
+        () if is("trace") => Some(Level::TRACE),
+        () if is("debug") => Some(Level::DEBUG),
+        () if is("info") => Some(Level::INFO),
+        () if is("warn") || is("warning") => Some(Level::WARN),
+        () if is("error") || is("err") => Some(Level::ERROR),
+        () => None,
+    }
+}
+
+/// The name of the [`LogLevelModifier`] with the given `level`.
+///
+/// This maps runtime levels to the type to use in `#[sysfail(...)]`
+/// attributes, for example in error messages or generated documentation.
+///
+/// ```rust
+/// use bevy_mod_sysfail::{level_to_modifier, parse_level};
+///
+/// assert_eq!(parse_level("warning").map(level_to_modifier), Some("Warn"));
+/// ```
+#[must_use]
+pub const fn level_to_modifier(level: Level) -> &'static str {
+    match level {
+        Level::TRACE => "Trace",
+        Level::DEBUG => "Debug",
+        Level::INFO => "Info",
+        Level::WARN => "Warn",
+        _ => "Error",
+    }
+}
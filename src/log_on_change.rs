@@ -0,0 +1,69 @@
+use std::{fmt, marker::PhantomData};
+
+use bevy_ecs::system::{Local, SystemParam};
+
+use crate::{
+    log_levels::Warn, log_simple::log_message, Callsite, Failure, Level, LogLevelModifier,
+    SysfailHooks,
+};
+
+/// Like [`LogSimply`](crate::prelude::LogSimply), but only log an error when
+/// it differs from the error of the previous run of the system.
+///
+/// This is useful for state machines, where the same error repeating each
+/// frame is noise, but a different error matters. The last error is stored
+/// in a `Local`, so this doesn't use `Time`, and each system has its own.
+/// When the system succeeds, the stored error is cleared, so the next error
+/// is logged, even if it is the same as before.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+/// use bevy_mod_sysfail::{prelude::*, testing::capture_logs};
+///
+/// #[sysfail(LogOnChange<&'static str, Error>)]
+/// fn failable_system(mut runs: Local<u32>) {
+///     *runs += 1;
+///     let () = Err(if *runs < 3 { "Loading" } else { "Failed to load" })?;
+/// }
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems(failable_system);
+/// let logs = capture_logs(|| (0..5).for_each(|_| schedule.run(&mut world)));
+/// let messages: Vec<_> = logs.iter().map(|(_, message)| message.as_str()).collect();
+/// assert_eq!(messages, ["Loading", "Failed to load"]);
+/// ```
+pub struct LogOnChange<T, Lvl = Warn>(pub T, PhantomData<Lvl>);
+
+impl<U: From<T>, T: fmt::Debug, L> From<T> for LogOnChange<U, L> {
+    fn from(t: T) -> Self {
+        Self(t.into(), PhantomData)
+    }
+}
+
+impl<T, Lvl> Failure for LogOnChange<T, Lvl>
+where
+    T: fmt::Display + PartialEq + Clone + Send + Sync + 'static,
+    Lvl: LogLevelModifier,
+{
+    type Param = (Local<'static, Option<T>>, SysfailHooks<'static>);
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(
+        self,
+        (mut last, hooks): <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        if last.as_ref() != Some(&self.0) {
+            log_message(hooks, Lvl::LEVEL, format_args!("{}", self.0), callsite);
+            *last = Some(self.0);
+        }
+    }
+
+    fn on_success((mut last, _): <Self::Param as SystemParam>::Item<'_, '_>) {
+        *last = None;
+    }
+}
@@ -0,0 +1,109 @@
+use std::{any::Any, fmt};
+
+use bevy_utils::tracing::callsite::DefaultCallsite;
+use bevy_utils::tracing::{subscriber::Interest, Metadata};
+
+use crate::dispatch::dispatch_message;
+use crate::{Callsite, Failure, Level, SysfailHooks};
+
+/// An error that decides its own log level, see [`RoutedLog`].
+pub trait LeveledError: fmt::Display {
+    /// The level at which to log this error.
+    fn level(&self) -> Level;
+}
+
+/// Log `T` at the level returned by [`LeveledError::level`].
+///
+/// Unlike [`LogSimply`](crate::prelude::LogSimply), where the level is a type
+/// parameter, the level here depends on the error _value_. Like `LogSimply`,
+/// errors are not deduplicated.
+///
+/// The `sysfail` macro defines a callsite per level for `RoutedLog`. When it
+/// can't, such as when `RoutedLog` is wrapped in another `Failure`, behind a
+/// type alias, or a type parameter of the system, errors are still logged at
+/// their level, but with the target and location of this crate.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_mod_sysfail::prelude::*;
+/// use bevy_mod_sysfail::{Level, LeveledError};
+/// use thiserror::Error;
+///
+/// #[derive(Error, Debug)]
+/// enum MyError {
+///     #[error("Not that bad")]
+///     Benign,
+///     #[error("Very bad")]
+///     Critical,
+/// }
+/// impl LeveledError for MyError {
+///     fn level(&self) -> Level {
+///         match self {
+///             MyError::Benign => Level::WARN,
+///             MyError::Critical => Level::ERROR,
+///         }
+///     }
+/// }
+///
+/// #[sysfail(RoutedLog<MyError>)]
+/// fn failable_system() {
+///     let () = Err(MyError::Critical)?;
+/// }
+/// ```
+pub struct RoutedLog<T>(pub T);
+
+impl<U: From<T>, T: fmt::Debug> From<T> for RoutedLog<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+/// One callsite per log level, all sharing the same file, line and target.
+///
+/// Generated by the `sysfail` macro for [`RoutedLog`].
+#[doc(hidden)]
+pub struct LeveledCallsites(pub [DefaultCallsite; 5]);
+
+impl LeveledCallsites {
+    /// The callsite of `level`.
+    #[must_use]
+    pub const fn get(&self, level: Level) -> &DefaultCallsite {
+        match level {
+            Level::TRACE => &self.0[0],
+            Level::DEBUG => &self.0[1],
+            Level::INFO => &self.0[2],
+            Level::WARN => &self.0[3],
+            Level::ERROR => &self.0[4],
+        }
+    }
+}
+impl Callsite for LeveledCallsites {
+    fn set_interest(&self, interest: Interest) {
+        for callsite in &self.0 {
+            callsite.set_interest(interest.clone());
+        }
+    }
+    fn metadata(&self) -> &Metadata<'_> {
+        self.get(Level::ERROR).metadata()
+    }
+}
+
+impl<T: LeveledError> Failure for RoutedLog<T> {
+    type Param = SysfailHooks<'static>;
+
+    /// Not used, the level is determined by [`LeveledError::level`].
+    const LEVEL: Level = Level::ERROR;
+
+    fn handle_error(self, hooks: SysfailHooks, callsite: Option<&'static impl Callsite>) {
+        if !hooks.enabled() {
+            return;
+        }
+        let level = self.0.level();
+        hooks.count(level);
+        // Without the callsites of the macro, log at `level` with ours.
+        let callsites = callsite.and_then(|c| <dyn Any>::downcast_ref::<LeveledCallsites>(c));
+        let meta = callsites.map(|callsites| callsites.get(level).metadata());
+        dispatch_message(meta, level, None, format_args!("{}", self.0));
+    }
+}
@@ -1,8 +1,9 @@
 use std::{fmt, marker::PhantomData};
 
-use bevy_utils::tracing::level_filters::{LevelFilter, STATIC_MAX_LEVEL};
-
-use crate::{log_levels::Warn, Callsite, Failure, Level, LogLevelModifier};
+use crate::{
+    dispatch::dispatch_message, log_levels::Warn, Callsite, Failure, Level, LogLevelModifier,
+    SysfailHooks,
+};
 
 /// Similar to [`Log`](crate::prelude::Log), but doesn't have any deduplication handling.
 ///
@@ -11,8 +12,52 @@ use crate::{log_levels::Warn, Callsite, Failure, Level, LogLevelModifier};
 ///
 /// However, if the same system returns an `Err` each frame, you will be _flooded_
 /// with error messages, so be warned.
+///
+/// Other than deduplication, errors are logged the same way as with `Log`:
+/// ```rust
+/// use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+/// use bevy_mod_sysfail::{prelude::*, testing::capture_logs, Level};
+///
+/// #[sysfail(Log<&'static str, Error>)]
+/// fn deduplicated() {
+///     sysfail_context!("attempt" => 1);
+///     let () = Err("Failed")?;
+/// }
+/// #[sysfail(LogSimply<&'static str, Error>)]
+/// fn simple() {
+///     sysfail_context!("attempt" => 1);
+///     let () = Err("Failed")?;
+/// }
+/// let mut world = World::new();
+/// world.init_resource::<Time>();
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems((deduplicated, simple).chain());
+///
+/// let logs = capture_logs(|| schedule.run(&mut world));
+/// let expected = (Level::ERROR, "Failed (attempt = 1)".to_owned());
+/// assert_eq!(logs, [expected.clone(), expected]);
+/// ```
 pub struct LogSimply<T, Lvl = Warn>(pub T, PhantomData<Lvl>);
 
+/// [`LogSimply`] for systems that run a single time, such as `Startup` systems.
+///
+/// Deduplication is pointless for systems that run once, so this avoids the
+/// `Res<Time>` and `HashMap` overhead of [`Log`](crate::prelude::Log).
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_mod_sysfail::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[sysfail(LogOnce<&'static str, Error>)]
+/// fn setup(mut commands: Commands) {
+///     let () = Err("Failed to setup")?;
+/// }
+/// ```
+pub type LogOnce<T, Lvl = Warn> = LogSimply<T, Lvl>;
+
 impl<U: From<T>, T: fmt::Debug, L> From<T> for LogSimply<U, L> {
     fn from(t: T) -> Self {
         Self(t.into(), PhantomData)
@@ -20,21 +65,28 @@ impl<U: From<T>, T: fmt::Debug, L> From<T> for LogSimply<U, L> {
 }
 
 impl<T: fmt::Display, Lvl: LogLevelModifier> Failure for LogSimply<T, Lvl> {
-    type Param = ();
+    type Param = SysfailHooks<'static>;
 
     const LEVEL: Level = Lvl::LEVEL;
 
-    fn handle_error(self, (): (), callsite: Option<&'static impl Callsite>) {
-        let meta = callsite.unwrap().metadata();
-        if Lvl::LEVEL <= STATIC_MAX_LEVEL && Lvl::LEVEL <= LevelFilter::current() {
-            let mut iter = meta.fields().iter();
-            bevy_utils::tracing::Event::dispatch(
-                meta,
-                &meta.fields().value_set(&[(
-                    &(iter.next().expect("FieldSet corrupted (this is a bug)")),
-                    Some(&format_args!("{}", self.0) as &dyn bevy_utils::tracing::field::Value),
-                )]),
-            );
-        }
+    fn handle_error(self, hooks: SysfailHooks, callsite: Option<&'static impl Callsite>) {
+        log_message(hooks, Lvl::LEVEL, format_args!("{}", self.0), callsite);
+    }
+}
+
+/// Log `message` at `level` using the `callsite` metadata, if `level` is enabled.
+///
+/// Nothing is logged or counted when `hooks` is disabled.
+pub(crate) fn log_message(
+    hooks: SysfailHooks,
+    level: Level,
+    message: fmt::Arguments,
+    callsite: Option<&'static impl Callsite>,
+) {
+    if !hooks.enabled() {
+        return;
     }
+    let meta = callsite.map(|callsite| callsite.metadata());
+    hooks.count(level);
+    dispatch_message(meta, level, None, message);
 }
@@ -0,0 +1,94 @@
+use std::{fmt, marker::PhantomData};
+
+use bevy_utils::tracing::Span;
+
+use crate::{
+    log_levels::Warn, log_simple::log_message, Callsite, Failure, Level, LogLevelModifier,
+    SysfailHooks,
+};
+
+/// Like [`LogSimply`](crate::prelude::LogSimply), but also mark the current
+/// span as failed, following the `tracing-opentelemetry` conventions.
+///
+/// Besides logging the error, this records `otel.status_code = "ERROR"` and
+/// `otel.status_message` (the error message) on [`Span::current`], so that
+/// OpenTelemetry exporters report the enclosing span as an error.
+/// Requires the `otel` feature.
+///
+/// `tracing` only records fields declared when the span is created, so the
+/// span must declare them with [`field::Empty`], as in
+/// `info_span!("frame", otel.status_code = Empty, otel.status_message = Empty)`.
+/// Otherwise, or when there is no current span, only the error is logged.
+///
+/// [`field::Empty`]: bevy_utils::tracing::field::Empty
+///
+/// # Example
+///
+/// ```rust
+/// use std::{fmt, sync::Mutex};
+///
+/// use bevy::log::tracing_subscriber::{layer::{Context, SubscriberExt}, Layer, Registry};
+/// use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+/// use bevy_mod_sysfail::prelude::*;
+/// use tracing::{field::{Empty, Field, Visit}, span};
+///
+/// static STATUS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+///
+/// // Collect the recorded `otel.status_code`s, like an exporter would
+/// struct RecordStatus;
+/// impl Visit for RecordStatus {
+///     fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+///         if field.name() == "otel.status_code" {
+///             STATUS.lock().unwrap().push(format!("{value:?}"));
+///         }
+///     }
+/// }
+/// impl<S: tracing::Subscriber> Layer<S> for RecordStatus {
+///     fn on_record(&self, _: &span::Id, values: &span::Record, _: Context<S>) {
+///         values.record(&mut RecordStatus);
+///     }
+/// }
+///
+/// #[sysfail(LogSpanError<&'static str, Error>)]
+/// fn failable_system() {
+///     let () = Err("Failed in a span")?;
+/// }
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems(failable_system);
+///
+/// tracing::subscriber::with_default(Registry::default().with(RecordStatus), || {
+///     // Without a current span, the error is only logged
+///     schedule.run(&mut world);
+///     assert!(STATUS.lock().unwrap().is_empty());
+///
+///     let span = info_span!("frame", otel.status_code = Empty, otel.status_message = Empty);
+///     span.in_scope(|| schedule.run(&mut world));
+/// });
+/// assert_eq!(*STATUS.lock().unwrap(), ["\"ERROR\""]);
+/// ```
+pub struct LogSpanError<T, Lvl = Warn>(pub T, PhantomData<Lvl>);
+
+impl<U: From<T>, T: fmt::Debug, L> From<T> for LogSpanError<U, L> {
+    fn from(t: T) -> Self {
+        Self(t.into(), PhantomData)
+    }
+}
+
+impl<T: fmt::Display, Lvl: LogLevelModifier> Failure for LogSpanError<T, Lvl> {
+    type Param = SysfailHooks<'static>;
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(self, hooks: SysfailHooks, callsite: Option<&'static impl Callsite>) {
+        if !hooks.enabled() {
+            return;
+        }
+        log_message(hooks, Lvl::LEVEL, format_args!("{}", self.0), callsite);
+        // No-op for the disabled span returned when there is no current span
+        let span = Span::current();
+        span.record("otel.status_code", "ERROR");
+        span.record("otel.status_message", format_args!("{}", self.0));
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::{fmt, marker::PhantomData};
+
+use crate::log_simple::log_message;
+use crate::{__macro::CountRuns, log_levels::Warn};
+use crate::{Callsite, Failure, Level, LogLevelModifier, SysfailHooks};
+
+/// The frame rate assumed by [`LogThrottled`].
+const ASSUMED_FPS: u32 = 60;
+
+/// Similar to [`LogSimply`](crate::prelude::LogSimply), but log at most about
+/// `PER_SEC` errors per second, without requiring `Res<Time>`.
+///
+/// Like `LogSimply`, this can be used in exclusive systems.
+///
+/// # Approximation
+///
+/// Time is not measured. Instead, `#[sysfail]` counts the runs of the system,
+/// whether they fail or not, and an error is logged only if no error was
+/// logged in the previous `60 / PER_SEC` runs. This assumes the system runs
+/// once per frame at 60 frames per second.
+///
+/// If the app runs slower, fewer errors are logged, never more. If `PER_SEC`
+/// is 60 or more, all errors are logged.
+///
+/// The runs are counted per system, so generic systems share their count
+/// between their instances. They are not counted when `LogThrottled` isn't the
+/// `Failure` of the system, such as in a `Tee` or when the `Failure` is a type
+/// parameter of the system, then all errors are logged, like `LogSimply`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+/// use bevy_mod_sysfail::{prelude::*, testing::capture_logs};
+///
+/// #[derive(Resource, Default)]
+/// struct Frame(u32);
+///
+/// #[sysfail(LogThrottled<&'static str, 20>)]
+/// fn failable_system(mut frame: ResMut<Frame>) {
+///     frame.0 += 1;
+///     if frame.0 % 2 == 0 {
+///         let () = Err("Logged at most about 20 times per second")?;
+///     }
+/// }
+/// let mut world = World::new();
+/// world.init_resource::<Frame>();
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems(failable_system);
+/// // Fails on frames 2, 4, 6, 8, 10 and 12
+/// let logs = capture_logs(|| (0..12).for_each(|_| schedule.run(&mut world)));
+/// // Logged at most once every 3 frames: on frames 2, 6 and 10
+/// assert_eq!(logs.len(), 3);
+/// ```
+pub struct LogThrottled<T, const PER_SEC: u32, Lvl = Warn>(
+    pub T,
+    Option<&'static SystemRuns>,
+    PhantomData<Lvl>,
+);
+
+/// How many times a `sysfail` system ran, and the last run an error was
+/// logged by [`LogThrottled`].
+#[doc(hidden)]
+pub struct SystemRuns {
+    runs: AtomicU32,
+    /// The last logged run plus one, `0` if no error was logged yet.
+    logged: AtomicU32,
+}
+impl SystemRuns {
+    /// A system that never ran.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { runs: AtomicU32::new(0), logged: AtomicU32::new(0) }
+    }
+    /// Count a run of the system.
+    pub fn advance(&self) {
+        self.runs.fetch_add(1, Ordering::Relaxed);
+    }
+    /// Whether to log an error of the current run, true if no error was
+    /// logged in the previous `every - 1` runs.
+    fn sample(&self, every: u32) -> bool {
+        let run = self.runs.load(Ordering::Relaxed);
+        let logged = self.logged.load(Ordering::Relaxed);
+        let should_log = logged == 0 || run.wrapping_sub(logged - 1) >= every;
+        if should_log {
+            self.logged
+                .store(run.wrapping_add(1).max(1), Ordering::Relaxed);
+        }
+        should_log
+    }
+}
+impl Default for SystemRuns {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: From<T>, T: fmt::Debug, L, const PER_SEC: u32> From<T> for LogThrottled<U, PER_SEC, L> {
+    fn from(t: T) -> Self {
+        Self(t.into(), None, PhantomData)
+    }
+}
+
+impl<T, const PER_SEC: u32, Lvl> CountRuns for LogThrottled<T, PER_SEC, Lvl> {
+    fn set_runs(&mut self, runs: &'static SystemRuns) {
+        self.1 = Some(runs);
+    }
+}
+
+impl<T, const PER_SEC: u32, Lvl> Failure for LogThrottled<T, PER_SEC, Lvl>
+where
+    T: fmt::Display,
+    Lvl: LogLevelModifier,
+{
+    type Param = SysfailHooks<'static>;
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(self, hooks: SysfailHooks, callsite: Option<&'static impl Callsite>) {
+        let every = (ASSUMED_FPS / PER_SEC.max(1)).max(1);
+        let should_log = self.1.map_or(true, |runs| runs.sample(every));
+        if should_log {
+            log_message(hooks, Lvl::LEVEL, format_args!("{}", self.0), callsite);
+        }
+    }
+}
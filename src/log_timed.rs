@@ -0,0 +1,77 @@
+use std::{fmt, marker::PhantomData};
+
+use bevy_utils::Duration;
+
+use crate::{__macro::Timed, LogLevelModifier};
+use crate::{log_levels::Warn, log_simple::log_message, Callsite, Failure, Level, SysfailHooks};
+
+/// Like [`LogSimply`](crate::prelude::LogSimply), but also log how long the
+/// system ran before failing.
+///
+/// `#[sysfail]` measures the time between the start of the system body and the
+/// error, this is only done when the `Failure` of the system is `LogTimed`,
+/// including through a type alias, and costs a single
+/// [`Instant::now`](bevy_utils::Instant::now) per run. The elapsed time is
+/// appended to the message in milliseconds, as `(after 1.234 ms)`.
+///
+/// When `LogTimed` is nested in another `Failure`, such as a `Tee`, or is a
+/// type parameter of the system, the time isn't measured, and the message is
+/// logged without it.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, testing::run_and_capture};
+///
+/// #[sysfail(LogTimed<&'static str>)]
+/// fn slow_system() {
+///     std::thread::sleep(std::time::Duration::from_millis(10));
+///     let () = Err("Slow failure")?;
+/// }
+/// let logs = run_and_capture(&mut World::new(), slow_system);
+/// let elapsed = logs[0].1.strip_prefix("Slow failure (after ").unwrap();
+/// let elapsed_ms: f64 = elapsed.strip_suffix(" ms)").unwrap().parse().unwrap();
+/// assert!(elapsed_ms >= 10.0);
+/// ```
+pub struct LogTimed<T, Lvl = Warn>(pub T, Option<Duration>, PhantomData<Lvl>);
+
+impl<T, Lvl> LogTimed<T, Lvl> {
+    /// How long the system ran before failing, if it was measured.
+    #[must_use]
+    pub const fn elapsed(&self) -> Option<Duration> {
+        self.1
+    }
+}
+
+impl<U: From<T>, T: fmt::Debug, L> From<T> for LogTimed<U, L> {
+    fn from(t: T) -> Self {
+        Self(t.into(), None, PhantomData)
+    }
+}
+
+impl<T, Lvl> Timed for LogTimed<T, Lvl> {
+    fn set_elapsed(&mut self, elapsed: Duration) {
+        self.1 = Some(elapsed);
+    }
+}
+
+impl<T: fmt::Display, Lvl: LogLevelModifier> Failure for LogTimed<T, Lvl> {
+    type Param = SysfailHooks<'static>;
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(self, hooks: SysfailHooks, callsite: Option<&'static impl Callsite>) {
+        let Some(elapsed) = self.1 else {
+            log_message(hooks, Lvl::LEVEL, format_args!("{}", self.0), callsite);
+            return;
+        };
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+        log_message(
+            hooks,
+            Lvl::LEVEL,
+            format_args!("{} (after {elapsed_ms:.3} ms)", self.0),
+            callsite,
+        );
+    }
+}
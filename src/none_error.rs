@@ -0,0 +1,20 @@
+use std::{error::Error, fmt, panic::Location};
+
+/// A `None` returned with `?` in a system with the `none_error` option of
+/// [`sysfail`](crate::sysfail).
+///
+/// The `Failure` type of a `none_error` system must implement
+/// `From<NoneError>`, this is the case of `Box<dyn Error>`, `anyhow::Error`
+/// and of the `Failure`s of this crate holding a `NoneError`, such as
+/// `Log<NoneError>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoneError {
+    /// Where the `None` was returned.
+    pub location: &'static Location<'static>,
+}
+impl fmt::Display for NoneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unexpected `None` at {}", self.location)
+    }
+}
+impl Error for NoneError {}
@@ -0,0 +1,43 @@
+use bevy_ecs::system::Resource;
+
+/// Observe errors handled by [`Log`](crate::prelude::Log), for example to
+/// collect metrics.
+///
+/// Insert this resource to get notified of each error handled by `Log`
+/// systems. When the resource is absent, `Log` has no additional overhead.
+///
+/// The hook is called with the system's log target and whether the error
+/// was `shown`. `shown` is `false` when the error was suppressed by the
+/// [`Dedup::cooldown`](crate::Dedup::cooldown). Note that suppressed errors
+/// are still reported to the observer. `shown` doesn't take into account
+/// the log level filter.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::SysfailObserver;
+///
+/// static SUPPRESSED: AtomicU64 = AtomicU64::new(0);
+///
+/// let mut app = App::new();
+/// app.insert_resource(SysfailObserver::new(|_target, shown| {
+///     if !shown {
+///         SUPPRESSED.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }));
+/// ```
+#[derive(Resource)]
+pub struct SysfailObserver(Box<dyn Fn(&'static str, bool) + Send + Sync>);
+
+impl SysfailObserver {
+    /// Call `on_error` each time a `Log` system handles an error.
+    pub fn new(on_error: impl Fn(&'static str, bool) + Send + Sync + 'static) -> Self {
+        Self(Box::new(on_error))
+    }
+    /// Notify the observer that an error was handled by the `target` system.
+    pub fn on_error(&self, target: &'static str, shown: bool) {
+        (self.0)(target, shown);
+    }
+}
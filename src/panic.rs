@@ -0,0 +1,77 @@
+use std::{any::Any, error::Error, fmt};
+
+use crate::{Callsite, Failure, Level, SysfailHooks};
+
+/// Panic with the error message.
+///
+/// This is mostly useful in debug builds, to catch errors early, see the
+/// `debug` and `release` options of [`sysfail`](crate::sysfail).
+///
+/// The system doesn't panic when [`SysfailEnabled`] is `false`.
+///
+/// [`SysfailEnabled`]: crate::SysfailEnabled
+///
+/// # Example
+///
+/// ```rust,should_panic
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail(Panic<&'static str>)]
+/// fn failable_system() {
+///     let () = Err("failed")?;
+/// }
+/// let mut app = App::new();
+/// app.add_systems(Update, failable_system);
+/// app.update();
+/// ```
+pub struct Panic<T>(pub T);
+
+impl<U: From<T>, T: fmt::Debug> From<T> for Panic<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+impl<T: fmt::Display> Failure for Panic<T> {
+    type Param = SysfailHooks<'static>;
+
+    const LEVEL: Level = Level::ERROR;
+
+    fn handle_error(self, hooks: SysfailHooks, _: Option<&'static impl Callsite>) {
+        if !hooks.enabled() {
+            return;
+        }
+        panic!("{}", self.0);
+    }
+}
+
+/// A panic caught by the `catch_panic` option of [`sysfail`](crate::sysfail).
+///
+/// The `Failure` type of a `catch_panic` system must implement
+/// `From<CaughtPanic>`, this is the case of `Box<dyn Error>` and `anyhow::Error`.
+#[derive(Debug, Clone)]
+pub struct CaughtPanic {
+    /// The panic message, if the panic payload is a string.
+    pub message: Option<String>,
+}
+impl CaughtPanic {
+    /// The `CaughtPanic` for the `payload` returned by `catch_unwind`.
+    #[must_use]
+    pub fn new(payload: &(dyn Any + Send)) -> Self {
+        let message = payload
+            .downcast_ref::<&'static str>()
+            .map(|message| (*message).to_owned())
+            .or_else(|| payload.downcast_ref::<String>().cloned());
+        Self { message }
+    }
+}
+impl fmt::Display for CaughtPanic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "System panicked: {message}"),
+            None => write!(f, "System panicked"),
+        }
+    }
+}
+impl Error for CaughtPanic {}
@@ -0,0 +1,135 @@
+use bevy::app::{App, Last, Plugin};
+use bevy_ecs::event::Event;
+
+use crate::deferred::{flush_deferred, Defer};
+use crate::emit::{flush_deduped_events, DedupedEvents};
+use crate::prelude::{LastError, SharedDedup};
+use crate::{Dedup, Failure, SysfailLevelCounts};
+
+/// Setup the events and resources used by the `sysfail` [`Failure`]s.
+///
+/// This plugin is optional, you can always register events and insert
+/// resources yourself. The `Failure`s of this crate that need setup, such as
+/// `Emit` with an unregistered event, panic with a message telling what to
+/// add to the plugin.
+///
+/// `SysfailPlugin` can be added several times, for example by different
+/// plugins of your app, each registering the events and resources they use.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[derive(Event)]
+/// struct MyErrorEvent;
+///
+/// let mut app = App::new();
+/// app.add_plugins(
+///     SysfailPlugin::default()
+///         .with_event::<MyErrorEvent>()
+///         .with_shared_dedup::<anyhow::Error>()
+///         .with_last_error()
+///         .count_errors(),
+/// );
+/// // Another plugin can add its own `SysfailPlugin`
+/// app.add_plugins(SysfailPlugin::default().with_event::<EntityError<&'static str>>());
+/// assert!(app.world.contains_resource::<LastError>());
+/// assert!(app.world.contains_resource::<bevy_mod_sysfail::SysfailLevelCounts>());
+/// ```
+#[derive(Default)]
+pub struct SysfailPlugin {
+    registrations: Vec<fn(&mut App)>,
+}
+impl SysfailPlugin {
+    /// Register the `E` event, so that it can be used with [`Emit<E>`].
+    ///
+    /// [`Emit<E>`]: crate::prelude::Emit
+    #[must_use]
+    pub fn with_event<E: Event>(mut self) -> Self {
+        self.registrations.push(|app| {
+            app.add_event::<E>();
+        });
+        self
+    }
+    /// Register the `E` event for [`EmitDeduped<E>`], with the
+    /// [`DedupedEvents<E>`] resource and the [`flush_deduped_events`] system,
+    /// in the `Last` schedule.
+    ///
+    /// [`EmitDeduped<E>`]: crate::prelude::EmitDeduped
+    /// [`DedupedEvents<E>`]: crate::prelude::DedupedEvents
+    /// [`flush_deduped_events`]: crate::prelude::flush_deduped_events
+    #[must_use]
+    pub fn with_deduped_event<E: Event>(mut self) -> Self {
+        self.registrations.push(|app| {
+            app.add_event::<E>()
+                .init_resource::<DedupedEvents<E>>()
+                .add_systems(Last, flush_deduped_events::<E>);
+        });
+        self
+    }
+    /// Insert the [`DeferredErrors<F>`] resource used by [`Defer<F>`],
+    /// and add the [`flush_deferred<F>`] system to the `Last` schedule.
+    ///
+    /// [`Defer<F>`]: crate::prelude::Defer
+    /// [`DeferredErrors<F>`]: crate::prelude::DeferredErrors
+    /// [`flush_deferred<F>`]: crate::prelude::flush_deferred
+    #[must_use]
+    pub fn with_deferred<F: Failure + Send + Sync + 'static>(mut self) -> Self {
+        self.registrations.push(|app| {
+            Defer::<F>::init(&mut app.world);
+            app.add_systems(Last, flush_deferred::<F>);
+        });
+        self
+    }
+    /// Insert the [`SharedDedup<T>`] resource used by [`LogShared<T>`].
+    ///
+    /// [`SharedDedup<T>`]: crate::prelude::SharedDedup
+    /// [`LogShared<T>`]: crate::prelude::LogShared
+    #[must_use]
+    pub fn with_shared_dedup<T: Dedup + 'static>(mut self) -> Self {
+        self.registrations.push(|app| {
+            app.init_resource::<SharedDedup<T>>();
+        });
+        self
+    }
+    /// Insert the [`SysfailLevelCounts`] resource, to count the errors logged
+    /// by `sysfail` systems.
+    #[must_use]
+    pub fn count_errors(mut self) -> Self {
+        self.registrations.push(|app| {
+            app.init_resource::<SysfailLevelCounts>();
+        });
+        self
+    }
+    /// Insert the [`LastError`] resource used by
+    /// [`StoreLast`](crate::prelude::StoreLast), so that it exists even before
+    /// the first error.
+    #[must_use]
+    pub fn with_last_error(mut self) -> Self {
+        self.registrations.push(|app| {
+            app.init_resource::<LastError>();
+        });
+        self
+    }
+    /// Setup the `World` for `F`, using [`Failure::init`].
+    ///
+    /// This is useful for custom `Failure`s whose [`Failure::Param`] requires
+    /// resources that must be initialized, for example with `FromWorld`.
+    #[must_use]
+    pub fn with_failure<F: Failure>(mut self) -> Self {
+        self.registrations.push(|app| F::init(&mut app.world));
+        self
+    }
+}
+impl Plugin for SysfailPlugin {
+    fn build(&self, app: &mut App) {
+        for register in &self.registrations {
+            register(app);
+        }
+    }
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,75 @@
+use linkme::distributed_slice;
+
+/// The location and `Failure` of a `sysfail` system, for tooling.
+///
+/// With the `registry` feature, each function with a `#[sysfail]` or
+/// `#[exclusive_sysfail]` attribute defines a `static SysfailSite`, listed
+/// by [`sysfail_sites`].
+#[derive(Debug)]
+pub struct SysfailSite {
+    /// The path of the system, such as `my_crate::my_module::my_system`.
+    pub system: &'static str,
+    /// The target of the errors logged by the system, the system path unless
+    /// the `target = "…"` option is used.
+    pub target: &'static str,
+    /// The file of the system.
+    pub file: &'static str,
+    /// The line of the `sysfail` attribute.
+    pub line: u32,
+    /// The `Failure` type of the system, as written in the attribute.
+    pub failure: &'static str,
+}
+impl SysfailSite {
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn new(
+        system: &'static str,
+        target: &'static str,
+        file: &'static str,
+        line: u32,
+        failure: &'static str,
+    ) -> Self {
+        Self { system, target, file, line, failure }
+    }
+}
+
+/// The `SysfailSite` of every `sysfail` system linked in the binary.
+#[doc(hidden)]
+#[distributed_slice]
+pub static SYSFAIL_SITES: [SysfailSite];
+
+/// All the `sysfail` systems of the binary, in no particular order.
+///
+/// Use this to list the `sysfail` systems of an app, for example in custom
+/// `tracing` tooling. Requires the `registry` feature.
+///
+/// The sites are collected by the linker, so this lists every `sysfail`
+/// system, including those that never ran or aren't added to any schedule,
+/// even at startup, before building the `App`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, sysfail_sites};
+///
+/// #[sysfail(Log<&'static str>)]
+/// fn failable_system() {
+///     let () = Err("Logged from a listed system")?;
+/// }
+/// #[sysfail(Ignore)]
+/// fn never_added() {}
+///
+/// let sites = sysfail_sites();
+/// let failable = sites
+///     .iter()
+///     .find(|site| site.system.ends_with("::failable_system"))
+///     .unwrap();
+/// assert!(failable.failure.starts_with("Log"));
+/// assert_eq!(failable.file, file!());
+/// assert!(sites.iter().any(|site| site.system.ends_with("::never_added")));
+/// ```
+#[must_use]
+pub fn sysfail_sites() -> &'static [SysfailSite] {
+    &SYSFAIL_SITES
+}
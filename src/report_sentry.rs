@@ -0,0 +1,64 @@
+use std::{fmt, marker::PhantomData};
+
+use crate::{log_levels::Error, Callsite, Failure, Level, LogLevelModifier};
+
+/// The `sentry` severity of `level`.
+///
+/// Sentry has no trace level, so `TRACE` maps to `Debug` like `DEBUG`.
+const fn sentry_level(level: Level) -> sentry::Level {
+    match level {
+        Level::TRACE | Level::DEBUG => sentry::Level::Debug,
+        Level::INFO => sentry::Level::Info,
+        Level::WARN => sentry::Level::Warning,
+        Level::ERROR => sentry::Level::Error,
+    }
+}
+
+/// Report the error to [Sentry](https://docs.rs/sentry), with
+/// `sentry::capture_message`, at the severity of `Lvl`.
+///
+/// This requires the `sentry` feature, and a Sentry client initialized with
+/// `sentry::init`. Without a client, errors are discarded.
+///
+/// This only reports errors, it doesn't log them. Combine it with a logging
+/// `Failure` in a [`Tee`](crate::prelude::Tee) to both log errors locally and
+/// report them remotely, as in the example. The error is then cloned.
+///
+/// `TRACE` and `DEBUG` are reported as `Debug`, `INFO` as `Info`, `WARN` as
+/// `Warning` and `ERROR` as `Error`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, testing::run_and_capture};
+///
+/// #[sysfail(Tee<Log<&'static str>, ReportSentry<&'static str, Warn>>)]
+/// fn failable_system() {
+///     let () = Err("Logged and reported")?;
+/// }
+/// let mut logs = Vec::new();
+/// let events = sentry::test::with_captured_events(|| {
+///     logs = run_and_capture(&mut World::new(), failable_system);
+/// });
+/// assert_eq!(logs[0].1, "Logged and reported");
+/// assert_eq!(events[0].message.as_deref(), Some("Logged and reported"));
+/// assert_eq!(events[0].level, sentry::Level::Warning);
+/// ```
+pub struct ReportSentry<T, Lvl = Error>(pub T, PhantomData<Lvl>);
+
+impl<U: From<T>, T: fmt::Debug, L> From<T> for ReportSentry<U, L> {
+    fn from(t: T) -> Self {
+        Self(t.into(), PhantomData)
+    }
+}
+
+impl<T: fmt::Display, Lvl: LogLevelModifier> Failure for ReportSentry<T, Lvl> {
+    type Param = ();
+
+    const LEVEL: Level = Lvl::LEVEL;
+
+    fn handle_error(self, (): (), _: Option<&'static impl Callsite>) {
+        sentry::capture_message(&self.0.to_string(), sentry_level(Lvl::LEVEL));
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use bevy_ecs::system::{Commands, SystemParam};
+
+use crate::{Callsite, Failure, Level};
+
+/// An error that edits the world when handled by [`RunCommand`].
+pub trait OnError {
+    /// Queue the world changes to apply when the error occurs, for example,
+    /// to despawn the entity that caused the error.
+    fn on_error(&self, commands: &mut Commands);
+}
+
+/// Call [`OnError::on_error`] with the system's [`Commands`].
+///
+/// As with all `Commands`, the changes are applied at the next
+/// `apply_deferred`, after the system ran, not immediately.
+///
+/// Use [`Tee`](crate::prelude::Tee) to also log the error.
+///
+/// # Example
+///
+/// ```rust
+/// use std::fmt;
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, testing::run_and_capture, OnError};
+///
+/// #[derive(Debug, Clone)]
+/// struct Unnamed(Entity);
+/// impl fmt::Display for Unnamed {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "{:?} has an empty name, despawning it", self.0)
+///     }
+/// }
+/// impl OnError for Unnamed {
+///     fn on_error(&self, commands: &mut Commands) {
+///         commands.entity(self.0).despawn();
+///     }
+/// }
+///
+/// #[sysfail(Tee<LogSimply<Unnamed>, RunCommand<Unnamed>>)]
+/// fn check_names(query: Query<(Entity, &Name)>) {
+///     for (entity, name) in &query {
+///         if name.is_empty() {
+///             let () = Err(Unnamed(entity))?;
+///         }
+///     }
+/// }
+/// let mut world = World::new();
+/// let unnamed = world.spawn(Name::new("")).id();
+/// let logs = run_and_capture(&mut world, check_names);
+/// assert_eq!(logs.len(), 1);
+/// assert!(world.get_entity(unnamed).is_none());
+/// ```
+pub struct RunCommand<T>(pub T);
+
+impl<U: From<T>, T: fmt::Debug> From<T> for RunCommand<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+impl<T: OnError> Failure for RunCommand<T> {
+    type Param = Commands<'static, 'static>;
+
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(
+        self,
+        mut commands: <Self::Param as SystemParam>::Item<'_, '_>,
+        _: Option<&'static impl Callsite>,
+    ) {
+        self.0.on_error(&mut commands);
+    }
+}
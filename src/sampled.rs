@@ -0,0 +1,121 @@
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+
+use bevy_ecs::system::{Local, SystemParam};
+use bevy_ecs::world::World;
+use bevy_utils::{hashbrown::HashMap, FixedState, PassHash};
+
+use crate::{log_simple::LogSimply, Callsite, Dedup, Failure, Level};
+
+/// A [`Failure`] holding a [`Dedup`] error, such as [`Log`](crate::prelude::Log).
+///
+/// This is used by [`Sampled`] to count errors per ID.
+pub trait DedupFailure: Failure {
+    /// The error held by this `Failure`.
+    type Error: Dedup;
+
+    /// The error held by this `Failure`.
+    fn error(&self) -> &Self::Error;
+}
+
+/// Only handle one in `N` errors with `F`, per error ID.
+///
+/// Errors are counted separately for each [`Dedup::identify`] ID. The first
+/// occurrence of an error is always handled, then every `N`th occurrence.
+///
+/// This composes with the time-based cooldown of [`Log`](crate::prelude::Log):
+/// the sampled errors are still subject to the cooldown.
+///
+/// # Example
+///
+/// Log one in 100 errors:
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail(Sampled<Log<&'static str>, 100>)]
+/// fn failable_system(query: Query<&Transform>) {
+///     let () = Err("failed")?;
+/// }
+/// ```
+///
+/// Use [`LogSimply`] for count-based sampling only, without cooldown:
+/// ```rust
+/// use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+/// use bevy_mod_sysfail::{prelude::*, testing::capture_logs};
+///
+/// #[sysfail(Sampled<LogSimply<&'static str>, 2>)]
+/// fn failable_system() {
+///     let () = Err("failed")?;
+/// }
+/// let mut world = World::new();
+/// let mut schedule = Schedule::default();
+/// schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+/// schedule.add_systems(failable_system);
+/// let logs = capture_logs(|| (0..5).for_each(|_| schedule.run(&mut world)));
+/// // The 1st, 3rd and 5th errors
+/// assert_eq!(logs.len(), 3);
+/// ```
+pub struct Sampled<F, const N: usize>(pub F);
+
+impl<T: fmt::Debug, F: From<T>, const N: usize> From<T> for Sampled<F, N> {
+    fn from(t: T) -> Self {
+        Self(F::from(t))
+    }
+}
+
+impl<F: DedupFailure, const N: usize> Failure for Sampled<F, N> {
+    type Param = (F::Param, Local<'static, HashMap<u64, usize, PassHash>>);
+
+    const LEVEL: Level = F::LEVEL;
+
+    fn handle_error(
+        self,
+        param: <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        if let Some(param) = sample::<F, N>(&self.0, param) {
+            self.0.handle_error(param, callsite);
+        }
+    }
+
+    fn handle_error_fallible(
+        self,
+        param: <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        match sample::<F, N>(&self.0, param) {
+            Some(param) => self.0.handle_error_fallible(param, callsite),
+            None => Ok(()),
+        }
+    }
+
+    fn on_success((param, _): <Self::Param as SystemParam>::Item<'_, '_>) {
+        F::on_success(param);
+    }
+
+    fn init(world: &mut World) {
+        F::init(world);
+    }
+}
+
+/// The `F::Param` if `failure` should be handled this time.
+fn sample<'w, 's, F: DedupFailure, const N: usize>(
+    failure: &F,
+    (param, mut counts): <<Sampled<F, N> as Failure>::Param as SystemParam>::Item<'w, 's>,
+) -> Option<<F::Param as SystemParam>::Item<'w, 's>> {
+    let mut hasher = FixedState.build_hasher();
+    failure.error().id_hash(&mut hasher);
+    let count = counts.entry(hasher.finish()).or_insert(0);
+    let handle = *count % N.max(1) == 0;
+    *count += 1;
+    handle.then_some(param)
+}
+
+impl<T: Dedup, Lvl: crate::LogLevelModifier> DedupFailure for LogSimply<T, Lvl> {
+    type Error = T;
+
+    fn error(&self) -> &T {
+        &self.0
+    }
+}
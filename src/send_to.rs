@@ -0,0 +1,69 @@
+use bevy_ecs::system::{lifetimeless::SRes, Local, Resource, SystemParam};
+use bevy_utils::tracing::warn;
+use crossbeam_channel::Sender;
+
+use crate::{Callsite, Failure, Level, WarnMissing};
+
+/// The sending end of the channel used by [`SendTo<T>`].
+///
+/// Insert it as a resource before running systems using `SendTo<T>`, and
+/// keep the receiving end wherever you want the errors to be handled.
+#[derive(Resource, Clone)]
+pub struct SysfailSender<T: Send + 'static>(pub Sender<T>);
+
+/// Send `T` errors through a [`crossbeam_channel`], requires the [`SysfailSender<T>`]
+/// resource.
+///
+/// If the `SysfailSender<T>` resource doesn't exist, or if the receiving end
+/// of the channel is dropped, the error is discarded, and a warning is logged
+/// the first time it happens in a given system.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[sysfail(SendTo<&'static str>)]
+/// fn failable_system() {
+///     let () = Err("Sent to another thread")?;
+/// }
+/// let (sender, receiver) = crossbeam_channel::unbounded();
+/// let mut app = App::new();
+/// app.insert_resource(SysfailSender(sender))
+///     .add_systems(Update, failable_system);
+/// app.update();
+/// assert_eq!(receiver.try_recv(), Ok("Sent to another thread"));
+/// ```
+pub struct SendTo<T>(pub T);
+
+impl<U: From<T>, T: std::fmt::Debug> From<T> for SendTo<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+impl<T: Send + 'static> Failure for SendTo<T> {
+    type Param = (
+        Option<SRes<SysfailSender<T>>>,
+        Local<'static, bool>,
+        WarnMissing<'static>,
+    );
+
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(
+        self,
+        (sender, mut warned, mut missing): <Self::Param as SystemParam>::Item<'_, '_>,
+        _: Option<&'static impl Callsite>,
+    ) {
+        let Some(sender) = sender else {
+            missing.warn::<SysfailSender<T>>("SendTo");
+            return;
+        };
+        if sender.0.send(self.0).is_err() && !*warned {
+            *warned = true;
+            warn!("The `SysfailSender` receiver was dropped, errors are now discarded");
+        }
+    }
+}
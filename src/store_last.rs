@@ -0,0 +1,82 @@
+use std::fmt;
+
+use bevy_ecs::system::{lifetimeless::SResMut, Commands, Resource, SystemParam};
+use bevy_ecs::world::World;
+use bevy_utils::Instant;
+
+use crate::{Callsite, Failure, Level, SysfailHooks};
+
+/// The most recent error stored by [`StoreLast`], and when it happened.
+///
+/// This is not generic over the error type, to make it easy to read from a
+/// single UI system. Clear it by setting it to `LastError(None)`.
+#[derive(Resource, Default, Debug, Clone)]
+pub struct LastError(pub Option<(Instant, String)>);
+
+/// Store the formatted error in the [`LastError`] resource, for example to
+/// display it in the UI.
+///
+/// `LastError` is inserted when a system first returns an error, if it doesn't
+/// exist already. Use `SysfailPlugin::default().with_failure::<StoreLast<T>>()`
+/// to insert it as `LastError(None)` at startup.
+///
+/// Use [`Tee`](crate::prelude::Tee) to also log the error. Nothing is stored
+/// when [`SysfailEnabled`](crate::SysfailEnabled) is `false`.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+/// use bevy::utils::Duration;
+///
+/// #[sysfail(StoreLast<&'static str>)]
+/// fn failable_system() {
+///     let () = Err("Stored in LastError")?;
+/// }
+///
+/// fn error_toast(mut last_error: ResMut<LastError>) {
+///     let Some((at, message)) = &last_error.0 else { return; };
+///     if at.elapsed() > Duration::from_secs(3) {
+///         last_error.0 = None;
+///     } else {
+///         println!("Error: {message}");
+///     }
+/// }
+/// ```
+pub struct StoreLast<T>(pub T);
+
+impl<U: From<T>, T: fmt::Debug> From<T> for StoreLast<U> {
+    fn from(t: T) -> Self {
+        Self(t.into())
+    }
+}
+
+impl<T: fmt::Display> Failure for StoreLast<T> {
+    type Param = (
+        Option<SResMut<LastError>>,
+        Commands<'static, 'static>,
+        SysfailHooks<'static>,
+    );
+
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(
+        self,
+        (last_error, mut commands, hooks): <Self::Param as SystemParam>::Item<'_, '_>,
+        _: Option<&'static impl Callsite>,
+    ) {
+        if !hooks.enabled() {
+            return;
+        }
+        let error = Some((Instant::now(), self.0.to_string()));
+        match last_error {
+            Some(mut last_error) => last_error.0 = error,
+            None => commands.insert_resource(LastError(error)),
+        }
+    }
+
+    fn init(world: &mut World) {
+        world.init_resource::<LastError>();
+    }
+}
@@ -0,0 +1,96 @@
+use std::{error::Error, fmt};
+
+use bevy_ecs::{system::SystemParam, world::World};
+
+use crate::{Callsite, Failure, Level};
+
+/// Handle the error with both `A` and `B`.
+///
+/// For example, to both log an error and send it as an event:
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::prelude::*;
+///
+/// #[derive(Event, Clone, Debug)]
+/// struct MyError;
+/// impl std::fmt::Display for MyError {
+///     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+///         write!(f, "My error")
+///     }
+/// }
+///
+/// #[sysfail(Tee<LogSimply<MyError>, Emit<MyError>>)]
+/// fn log_and_emit() {
+///     let () = Err(MyError)?;
+/// }
+/// ```
+///
+/// The error is converted into both `A` and `B`, so it must be [`Clone`].
+///
+/// # Callsite
+///
+/// The callsite is passed to both `A` and `B`. It is generated if either `A`
+/// or `B` is a logging `Failure`, using its log level. If both `A` and `B`
+/// log, the callsite uses the level of `A`.
+///
+/// # Fallible handlers
+///
+/// If handling the error with `A` fails, it is still handled with `B`.
+/// When both fail, their errors are returned together, as
+/// `"<error of A>, and <error of B>"`.
+pub struct Tee<A, B>(pub A, pub B);
+
+/// The errors of both `A` and `B`, when they fail to handle the error of
+/// a [`Tee<A, B>`].
+#[derive(Debug)]
+struct BothFailed(Box<dyn Error>, Box<dyn Error>);
+impl fmt::Display for BothFailed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, and {}", self.0, self.1)
+    }
+}
+impl Error for BothFailed {}
+
+impl<T: Clone, A: From<T>, B: From<T>> From<T> for Tee<A, B> {
+    fn from(value: T) -> Self {
+        Self(A::from(value.clone()), B::from(value))
+    }
+}
+
+impl<A: Failure, B: Failure> Failure for Tee<A, B> {
+    type Param = (A::Param, B::Param);
+
+    const LEVEL: Level = A::LEVEL;
+
+    fn handle_error(
+        self,
+        (a_param, b_param): <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) {
+        self.0.handle_error(a_param, callsite);
+        self.1.handle_error(b_param, callsite);
+    }
+
+    fn handle_error_fallible(
+        self,
+        (a_param, b_param): <Self::Param as SystemParam>::Item<'_, '_>,
+        callsite: Option<&'static impl Callsite>,
+    ) -> Result<(), Box<dyn Error>> {
+        let a = self.0.handle_error_fallible(a_param, callsite);
+        let b = self.1.handle_error_fallible(b_param, callsite);
+        match (a, b) {
+            (Err(a), Err(b)) => Err(Box::new(BothFailed(a, b))),
+            (a, b) => a.and(b),
+        }
+    }
+
+    fn on_success((a_param, b_param): <Self::Param as SystemParam>::Item<'_, '_>) {
+        A::on_success(a_param);
+        B::on_success(b_param);
+    }
+
+    fn init(world: &mut World) {
+        A::init(world);
+        B::init(world);
+    }
+}
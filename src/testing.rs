@@ -0,0 +1,107 @@
+//! Utilities to test `sysfail` systems.
+//!
+//! Use [`run_and_capture`] or [`capture_logs`] to get the list of errors
+//! logged by `sysfail` systems.
+//!
+//! This uses a thread-local `tracing` subscriber, so it doesn't conflict with
+//! the global subscriber, and can be used in parallel `#[test]`s. Only
+//! events logged on the current thread are captured, this is why `run_and_capture`
+//! runs the system on the current thread.
+//!
+//! `sysfail` events are recognized by the name of their metadata, which starts
+//! with `"sysfail "`. The context fields of the events, recorded with
+//! `sysfail_context!` or `sysfail_tag!`, are appended to the captured message,
+//! as `" (key = value, ...)"`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy_mod_sysfail::prelude::*;
+//! use bevy_mod_sysfail::{testing::run_and_capture, Level};
+//!
+//! #[sysfail(LogSimply<&'static str, Error>)]
+//! fn failable_system() {
+//!     let () = Err("This failed")?;
+//! }
+//! let mut world = World::new();
+//! let logs = run_and_capture(&mut world, failable_system);
+//! assert_eq!(logs, [(Level::ERROR, "This failed".to_owned())]);
+//! ```
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bevy_ecs::system::{IntoSystem, RunSystemOnce};
+use bevy_ecs::world::World;
+use bevy_utils::tracing::field::{Field, Visit};
+use bevy_utils::tracing::span::{Attributes, Id, Record};
+use bevy_utils::tracing::{subscriber, Event, Metadata, Subscriber};
+
+use crate::Level;
+
+/// Run `system` once in `world`, returning the errors logged by `sysfail`.
+pub fn run_and_capture<M>(
+    world: &mut World,
+    system: impl IntoSystem<(), (), M>,
+) -> Vec<(Level, String)> {
+    capture_logs(|| world.run_system_once(system))
+}
+
+/// Run `f`, returning the errors logged by `sysfail` on the current thread.
+///
+/// Events not emitted by a `sysfail` system, such as `warn!` or `info!` calls,
+/// are not captured.
+pub fn capture_logs(f: impl FnOnce()) -> Vec<(Level, String)> {
+    let captured = Arc::default();
+    subscriber::with_default(Capture(Arc::clone(&captured)), f);
+    let mut captured = captured.lock().unwrap_or_else(PoisonError::into_inner);
+    std::mem::take(&mut *captured)
+}
+
+/// The metadata name of `sysfail` callsites starts with this.
+const SYSFAIL_NAME_PREFIX: &str = "sysfail ";
+
+struct Capture(Arc<Mutex<Vec<(Level, String)>>>);
+
+/// Records the message of `sysfail` events, their first recorded field,
+/// followed by their context fields.
+///
+/// The field is named `"message"`, unless the `field` option is used.
+struct MessageVisitor(Option<String>, Vec<String>);
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if self.0.is_none() {
+            self.0 = Some(format!("{value:?}"));
+        } else if field.name() != "sysfail" {
+            self.1.push(format!("{field} = {value:?}"));
+        }
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let meta = event.metadata();
+        if !meta.name().starts_with(SYSFAIL_NAME_PREFIX) {
+            return;
+        }
+        let mut visitor = MessageVisitor(None, Vec::new());
+        event.record(&mut visitor);
+        if let Some(mut message) = visitor.0 {
+            if !visitor.1.is_empty() {
+                message = format!("{message} ({})", visitor.1.join(", "));
+            }
+            let mut captured = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+            captured.push((*meta.level(), message));
+        }
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
@@ -0,0 +1,39 @@
+use std::{error::Error, fmt};
+
+/// A stand-in for `()` errors, displayed as `"error"`.
+///
+/// `()` doesn't implement `Display`, and can't, so `Log<()>` doesn't compile.
+/// This is a convenience for prototypes, where `Err(())?` is common: use
+/// `Log<UnitError>` instead, it converts from `()`. Since `()` carries no
+/// information, all `UnitError`s of a system share the same [`Dedup`] ID, `()`.
+/// Use [`Ignore`](crate::prelude::Ignore) to not log `()` errors at all.
+///
+/// [`Dedup`]: crate::Dedup
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_mod_sysfail::{prelude::*, testing::run_and_capture, UnitError};
+///
+/// #[sysfail(Log<UnitError>)]
+/// fn delete_gizmo(gizmos: Query<Entity, With<Transform>>) {
+///     let _gizmo = gizmos.iter().next().ok_or(())?;
+/// }
+/// let mut world = World::new();
+/// let logs = run_and_capture(&mut world, delete_gizmo);
+/// assert_eq!(logs[0].1, "error");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UnitError;
+impl fmt::Display for UnitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error")
+    }
+}
+impl Error for UnitError {}
+impl From<()> for UnitError {
+    fn from((): ()) -> Self {
+        Self
+    }
+}
@@ -0,0 +1,33 @@
+use std::any::type_name;
+
+use bevy_ecs::system::{Local, Resource, SystemName, SystemParam};
+use bevy_utils::tracing::warn;
+
+/// Warn once per system that a resource required by a [`Failure`] is missing.
+///
+/// Use it with an `Option<Res<R>>` in [`Failure::Param`], so that the
+/// `Failure` doesn't panic when `R` doesn't exist, but discards the error.
+/// See the [`Failure::Param`] documentation for an example.
+///
+/// [`Failure`]: crate::Failure
+/// [`Failure::Param`]: crate::Failure::Param
+#[derive(SystemParam)]
+pub struct WarnMissing<'s> {
+    system: SystemName<'s>,
+    warned: Local<'s, bool>,
+}
+impl WarnMissing<'_> {
+    /// Log a warning naming the missing `R` resource, the `failure` and the
+    /// system, if it wasn't logged already for this system.
+    pub fn warn<R: Resource>(&mut self, failure: &str) {
+        if !*self.warned {
+            *self.warned = true;
+            warn!(
+                "The `{resource}` resource used by `{failure}` in the `{system}` system \
+                doesn't exist, errors are discarded",
+                resource = type_name::<R>(),
+                system = self.system.name(),
+            );
+        }
+    }
+}
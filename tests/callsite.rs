@@ -0,0 +1,86 @@
+use std::any::type_name;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{fmt, marker::PhantomData};
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use bevy_mod_sysfail::{prelude::*, testing::run_and_capture, Callsite, Level};
+
+type GameLog = Log<&'static str, Error>;
+
+#[sysfail(GameLog)]
+fn aliased_log() {
+    let () = Err("Logged through an alias")?;
+}
+
+#[test]
+fn aliased_log_has_callsite() {
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    let logs = run_and_capture(&mut world, aliased_log);
+    assert_eq!(logs, [(Level::ERROR, "Logged through an alias".to_owned())]);
+}
+
+static HAD_CALLSITE: AtomicBool = AtomicBool::new(false);
+
+struct ScreenLog;
+impl From<&'static str> for ScreenLog {
+    fn from(_: &'static str) -> Self {
+        Self
+    }
+}
+impl Failure for ScreenLog {
+    type Param = ();
+    const LEVEL: Level = Level::INFO;
+
+    fn handle_error(self, (): (), callsite: Option<&'static impl Callsite>) {
+        let meta = callsite.expect("custom failures get a callsite").metadata();
+        assert_eq!(*meta.level(), Level::INFO);
+        assert!(meta.target().ends_with("screen_log"));
+        HAD_CALLSITE.store(true, Ordering::Relaxed);
+    }
+}
+
+#[sysfail(ScreenLog)]
+fn screen_log() {
+    let () = Err("Shown on screen")?;
+}
+
+#[test]
+fn custom_log_has_callsite() {
+    run_and_capture(&mut World::new(), screen_log);
+    assert!(HAD_CALLSITE.load(Ordering::Relaxed));
+}
+
+struct Missing<T>(PhantomData<T>);
+impl<T> fmt::Debug for Missing<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Missing({})", type_name::<T>())
+    }
+}
+impl<T> fmt::Display for Missing<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Missing {}", type_name::<T>())
+    }
+}
+
+#[sysfail(LogSimply<Missing<T>, Error>)]
+fn generic_log<T: Send + Sync + 'static>() {
+    let () = Err(Missing::<T>(PhantomData))?;
+}
+
+// An unknown `Failure` depending on `T`, so the system has no callsite.
+type GenericLog<T> = LogSimply<Missing<T>, Error>;
+
+#[sysfail(GenericLog<T>)]
+fn generic_alias<T: Send + Sync + 'static>() {
+    let () = Err(Missing::<T>(PhantomData))?;
+}
+
+#[test]
+fn generic_failure_callsite() {
+    let mut world = World::new();
+    let logs = run_and_capture(&mut world, generic_log::<u32>);
+    assert_eq!(logs, [(Level::ERROR, "Missing u32".to_owned())]);
+    // Logged without the callsite of the system, this shouldn't panic.
+    world.run_system_once(generic_alias::<u32>);
+}
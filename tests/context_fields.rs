@@ -0,0 +1,98 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use bevy_mod_sysfail::prelude::*;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{subscriber, Event, Metadata, Subscriber};
+
+/// The `(name, value)` of the fields of an event.
+type Fields = Vec<(String, String)>;
+
+/// Records the fields of every event.
+struct Capture(Arc<Mutex<Vec<Fields>>>);
+
+struct FieldsVisitor(Fields);
+impl Visit for FieldsVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.push((field.name().to_owned(), format!("{value:?}")));
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = FieldsVisitor(Vec::new());
+        event.record(&mut visitor);
+        self.0.lock().unwrap().push(visitor.0);
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+fn capture_fields<M>(system: impl IntoSystem<(), (), M>) -> Vec<Fields> {
+    let captured = Arc::default();
+    let capture = Capture(Arc::clone(&captured));
+    subscriber::with_default(capture, || World::new().run_system_once(system));
+    let mut captured = captured.lock().unwrap();
+    std::mem::take(&mut *captured)
+}
+
+fn fields(fields: &[(&str, &str)]) -> Vec<Fields> {
+    let fields = fields
+        .iter()
+        .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()));
+    vec![fields.collect()]
+}
+
+#[sysfail(Log<&'static str>)]
+fn handle_request() {
+    let conn_id = 42;
+    sysfail_tag!(conn_id);
+    for attempt in 0..3 {
+        sysfail_context!("attempt" => attempt);
+    }
+    let () = Err("Invalid request")?;
+}
+
+#[test]
+fn context_as_fields() {
+    let expected = [
+        ("message", "Invalid request"),
+        ("conn_id", "42"),
+        ("attempt", "2"),
+    ];
+    assert_eq!(capture_fields(handle_request), fields(&expected));
+}
+
+macro_rules! record_attempt {
+    ($attempt:expr) => {
+        sysfail_context!("attempt" => $attempt)
+    };
+}
+
+#[sysfail(LogSimply<&'static str>)]
+fn retry_request() {
+    let conn_id = 42;
+    sysfail_tag!(conn_id);
+    record_attempt!(3);
+    let () = Err("Invalid request")?;
+}
+
+#[test]
+fn unseen_keys_as_text() {
+    // `sysfail` doesn't see the `"attempt"` key, recorded by another macro.
+    let expected = [
+        ("message", "Invalid request (attempt = 3)"),
+        ("conn_id", "42"),
+    ];
+    assert_eq!(capture_fields(retry_request), fields(&expected));
+}
@@ -0,0 +1,84 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use bevy_mod_sysfail::{prelude::*, Level, LeveledError};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{subscriber, Event, Metadata, Subscriber};
+
+/// Records the target and level of every event.
+struct Capture(Arc<Mutex<Vec<(&'static str, Level)>>>);
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let meta = event.metadata();
+        self.0.lock().unwrap().push((meta.target(), *meta.level()));
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+/// Run `system` twice, each time with a new system state, then flush the
+/// deferred errors, returning the target and level of the logged events.
+fn run_deferred<F, M>(system: impl IntoSystem<(), (), M> + Copy) -> Vec<(&'static str, Level)>
+where
+    F: Failure + Send + Sync + 'static,
+{
+    let mut world = World::new();
+    world.init_resource::<DeferredErrors<F>>();
+    world.run_system_once(system);
+    world.run_system_once(system);
+    assert_eq!(world.resource::<DeferredErrors<F>>().len(), 2);
+
+    let captured = Arc::default();
+    let capture = Capture(Arc::clone(&captured));
+    subscriber::with_default(capture, || world.run_system_once(flush_deferred::<F>));
+    let mut captured = captured.lock().unwrap();
+    std::mem::take(&mut *captured)
+}
+
+#[sysfail(Defer<LogSimply<&'static str>>, target = "game::net")]
+fn deferred() {
+    let () = Err("Deferred")?;
+}
+
+#[test]
+fn keeps_callsite() {
+    let logs = run_deferred::<LogSimply<&'static str>, _>(deferred);
+    assert_eq!(logs, [("game::net", Level::WARN); 2]);
+}
+
+struct Routed;
+impl std::fmt::Display for Routed {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Routed")
+    }
+}
+impl From<&'static str> for Routed {
+    fn from(_: &'static str) -> Self {
+        Self
+    }
+}
+impl LeveledError for Routed {
+    fn level(&self) -> Level {
+        Level::INFO
+    }
+}
+
+#[sysfail(Defer<RoutedLog<Routed>>, target = "game::net")]
+fn deferred_routed() {
+    let () = Err("Deferred")?;
+}
+
+#[test]
+fn keeps_leveled_callsites() {
+    let logs = run_deferred::<RoutedLog<Routed>, _>(deferred_routed);
+    assert_eq!(logs, [("game::net", Level::INFO); 2]);
+}
@@ -0,0 +1,73 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use bevy_mod_sysfail::{prelude::*, testing::run_and_capture, SysfailEnabled};
+
+fn disabled_world() -> World {
+    let mut world = World::new();
+    world.insert_resource(SysfailEnabled(false));
+    world
+}
+
+#[sysfail(Panic<&'static str>)]
+fn panicking() {
+    let () = Err("Doesn't panic while disabled")?;
+}
+
+#[test]
+fn disabled_panic() {
+    disabled_world().run_system_once(panicking);
+}
+
+#[sysfail(StoreLast<&'static str>)]
+fn stored() {
+    let () = Err("Not stored while disabled")?;
+}
+
+#[test]
+fn disabled_store_last() {
+    let mut world = disabled_world();
+    world.run_system_once(stored);
+    assert!(world.get_resource::<LastError>().is_none());
+
+    world.resource_mut::<SysfailEnabled>().0 = true;
+    world.run_system_once(stored);
+    assert!(world.resource::<LastError>().0.is_some());
+}
+
+#[sysfail(LogSimply<&'static str>)]
+fn check_all(query: Query<&Name>) {
+    for name in &query {
+        if name.as_str().is_empty() {
+            sysfail_handle!("Found an empty name");
+        }
+    }
+}
+
+#[test]
+fn disabled_handle() {
+    let mut world = disabled_world();
+    world.spawn_batch([Name::new(""), Name::new("")]);
+    assert!(run_and_capture(&mut world, check_all).is_empty());
+
+    world.resource_mut::<SysfailEnabled>().0 = true;
+    assert_eq!(run_and_capture(&mut world, check_all).len(), 2);
+}
+
+#[exclusive_sysfail(LogSimply<&'static str>)]
+fn exclusive_check_all(world: &mut World) {
+    let mut query = world.query::<&Name>();
+    for name in query.iter(world) {
+        if name.as_str().is_empty() {
+            sysfail_handle!("Found an empty name");
+        }
+    }
+}
+
+#[test]
+fn disabled_exclusive_handle() {
+    let mut world = disabled_world();
+    world.spawn_batch([Name::new(""), Name::new("")]);
+    assert!(run_and_capture(&mut world, exclusive_check_all).is_empty());
+
+    world.resource_mut::<SysfailEnabled>().0 = true;
+    assert_eq!(run_and_capture(&mut world, exclusive_check_all).len(), 2);
+}
@@ -0,0 +1,44 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use bevy_mod_sysfail::{prelude::*, SysfailEnabled};
+
+#[sysfail(LogOrPanic<&'static str, Error>)]
+fn failable_system() {
+    let () = Err("Should fail the test")?;
+}
+
+type TestLog = LogOrPanic<&'static str>;
+
+#[sysfail(TestLog)]
+fn aliased_system() {
+    let () = Err("Should also fail the test")?;
+}
+
+#[sysfail(Tee<LogOrPanic<&'static str>, Ignore>)]
+fn tee_system() {
+    let () = Err("Should fail the test through Tee")?;
+}
+
+#[test]
+#[should_panic(expected = "Should fail the test")]
+fn panics_in_tests() {
+    World::new().run_system_once(failable_system);
+}
+
+#[test]
+#[should_panic(expected = "Should also fail the test")]
+fn aliased_panics_in_tests() {
+    World::new().run_system_once(aliased_system);
+}
+
+#[test]
+#[should_panic(expected = "Should fail the test through Tee")]
+fn tee_panics_in_tests() {
+    World::new().run_system_once(tee_system);
+}
+
+#[test]
+fn disabled_doesnt_panic() {
+    let mut world = World::new();
+    world.insert_resource(SysfailEnabled(false));
+    world.run_system_once(failable_system);
+}
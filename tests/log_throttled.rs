@@ -0,0 +1,52 @@
+use bevy::{ecs::schedule::ExecutorKind, prelude::*};
+use bevy_mod_sysfail::{prelude::*, testing::capture_logs};
+
+#[derive(Resource, Default)]
+struct Frame(u32);
+
+/// Run the `system` schedule for `frames` frames, returning the logged errors.
+fn run_frames<M>(frames: u32, system: impl IntoSystemConfigs<M>) -> usize {
+    let mut world = World::new();
+    world.init_resource::<Frame>();
+    let mut schedule = Schedule::default();
+    schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    schedule.add_systems(system);
+    capture_logs(|| (0..frames).for_each(|_| schedule.run(&mut world))).len()
+}
+
+#[sysfail(LogThrottled<&'static str, 20>)]
+fn every_third_frame(mut frame: ResMut<Frame>) {
+    frame.0 += 1;
+    if frame.0 % 3 == 0 {
+        let () = Err("Fails 20 times per second")?;
+    }
+}
+
+#[test]
+fn samples_frames() {
+    // 60 frames are a second, failing 20 times, all of them logged
+    assert_eq!(run_frames(60, every_third_frame), 20);
+}
+
+type Throttled = LogThrottled<&'static str, 10>;
+
+#[sysfail(Throttled)]
+fn every_frame() {
+    let () = Err("Fails 60 times per second")?;
+}
+
+#[test]
+fn aliased() {
+    assert_eq!(run_frames(60, every_frame), 10);
+}
+
+#[exclusive_sysfail(LogThrottled<&'static str, 10>)]
+fn exclusive_every_frame(world: &mut World) {
+    world.resource_mut::<Frame>().0 += 1;
+    let () = Err("Fails 60 times per second")?;
+}
+
+#[test]
+fn exclusive() {
+    assert_eq!(run_frames(60, exclusive_every_frame), 10);
+}
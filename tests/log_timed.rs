@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use bevy_mod_sysfail::{prelude::*, testing::run_and_capture};
+
+/// Whether `message` is `"Failed (after <ms> ms)"`.
+fn is_timed(message: &str) -> bool {
+    let elapsed = message.strip_prefix("Failed (after ");
+    let elapsed = elapsed.and_then(|elapsed| elapsed.strip_suffix(" ms)"));
+    elapsed.is_some_and(|elapsed| elapsed.parse::<f64>().is_ok())
+}
+
+type Timed = LogTimed<&'static str>;
+
+#[sysfail(Timed)]
+fn aliased() {
+    let () = Err("Failed")?;
+}
+
+#[test]
+fn aliased_is_timed() {
+    let logs = run_and_capture(&mut World::new(), aliased);
+    assert!(is_timed(&logs[0].1), "{logs:?}");
+}
+
+#[exclusive_sysfail(LogTimed<&'static str>)]
+fn exclusive(_world: &mut World) {
+    let () = Err("Failed")?;
+}
+
+#[test]
+fn exclusive_is_timed() {
+    let logs = run_and_capture(&mut World::new(), exclusive);
+    assert!(is_timed(&logs[0].1), "{logs:?}");
+}
+
+#[sysfail(Tee<LogTimed<&'static str>, Ignore>)]
+fn nested() {
+    let () = Err("Failed")?;
+}
+
+#[test]
+fn nested_is_not_timed() {
+    let logs = run_and_capture(&mut World::new(), nested);
+    assert_eq!(logs[0].1, "Failed");
+}
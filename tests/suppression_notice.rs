@@ -0,0 +1,83 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_mod_sysfail::prelude::*;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{subscriber, Event, Level, Metadata, Subscriber};
+
+/// Records the level and message of every event, `sysfail` or not.
+struct Capture(Arc<Mutex<Vec<(Level, String)>>>);
+
+struct MessageVisitor(Option<String>);
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, _: &Field, value: &dyn fmt::Debug) {
+        if self.0.is_none() {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = MessageVisitor(None);
+        event.record(&mut visitor);
+        if let Some(message) = visitor.0 {
+            self.0
+                .lock()
+                .unwrap()
+                .push((*event.metadata().level(), message));
+        }
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+#[sysfail(Log<&'static str, Error>)]
+fn failing_system() {
+    let () = Err("Always fails")?;
+}
+
+#[test]
+fn notice_with_advancing_clock() {
+    let shown = || vec![(Level::ERROR, "Always fails".to_owned())];
+    let notice = || {
+        let text = "Suppressing identical errors of suppression_notice::failing_system until none occur for 1s";
+        vec![(Level::TRACE, text.to_owned())]
+    };
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    // Keep the same system, so that its `Log` state persists between runs.
+    let mut system = IntoSystem::into_system(failing_system);
+    system.initialize(&mut world);
+    let mut run_at = |world: &mut World, millis| {
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(millis));
+        let captured = Arc::default();
+        let capture = Capture(Arc::clone(&captured));
+        subscriber::with_default(capture, || system.run((), world));
+        let mut captured = captured.lock().unwrap();
+        std::mem::take(&mut *captured)
+    };
+
+    assert_eq!(run_at(&mut world, 0), shown());
+    // Announced once, with the cooldown, not the time left since 0ms.
+    assert_eq!(run_at(&mut world, 500), notice());
+    // Each error restarts the cooldown, the suppression isn't announced again.
+    assert_eq!(run_at(&mut world, 900), []);
+    assert_eq!(run_at(&mut world, 900), []);
+    // 1s without errors since 2300ms.
+    assert_eq!(run_at(&mut world, 1000), shown());
+    assert_eq!(run_at(&mut world, 100), notice());
+}
@@ -0,0 +1,522 @@
+use std::any::type_name;
+use std::convert::Infallible;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::OnceLock;
+use std::{fmt, marker::PhantomData, rc::Rc};
+
+use bevy::{ecs::system::RunSystemOnce, prelude::*};
+use bevy_mod_sysfail::{
+    prelude::*, testing::run_and_capture, Dedup, Level, LeveledError, SysfailObserver,
+};
+
+#[sysfail(Log<&'static str>, target = "game::net")]
+fn custom_target() {
+    let () = Err("failed")?;
+}
+
+#[test]
+fn target_option() {
+    static TARGET: OnceLock<&'static str> = OnceLock::new();
+
+    let mut world = World::new();
+    world.insert_resource(SysfailObserver::new(|target, _| {
+        let _ = TARGET.set(target);
+    }));
+    world.run_system_once(custom_target);
+    assert_eq!(TARGET.get(), Some(&"game::net"));
+}
+
+#[sysfail(LogSimply<&'static str>, field = "error")]
+fn custom_field() {
+    let () = Err("failed")?;
+}
+
+#[test]
+fn field_option() {
+    let logs = run_and_capture(&mut World::new(), custom_field);
+    assert_eq!(logs[0].1, "failed");
+}
+
+#[sysfail(Log<anyhow::Error>, catch_panic)]
+#[allow(unreachable_code)]
+fn panicking_system() {
+    panic!("Oh no");
+}
+
+#[test]
+fn catch_panic_option() {
+    let logs = run_and_capture(&mut World::new(), panicking_system);
+    assert_eq!(logs[0].1, "System panicked: Oh no");
+}
+
+#[exclusive_sysfail(Log<&'static str, Error>, nodedup)]
+fn exclusive_system(_world: &mut World) {
+    let () = Err("Not deduplicated")?;
+}
+
+#[test]
+fn nodedup_option() {
+    let logs = run_and_capture(&mut World::new(), exclusive_system);
+    assert_eq!(logs, [(Level::ERROR, "Not deduplicated".to_owned())]);
+}
+
+fn check_name(name: &Name) -> Result<(), &'static str> {
+    if name.is_empty() {
+        Err("Empty name")
+    } else {
+        Ok(())
+    }
+}
+
+#[sysfail(LogSimply<&'static str>)]
+fn check_first(query: Query<&Name>) {
+    let Some(name) = query.iter().next() else {
+        return;
+    };
+    if name.as_str() == "admin" {
+        return Err("Reserved name")?;
+    }
+    check_name(name)
+}
+
+#[test]
+fn early_returns() {
+    let mut world = World::new();
+    assert!(run_and_capture(&mut world, check_first).is_empty());
+    world.spawn(Name::new("admin"));
+    let logs = run_and_capture(&mut world, check_first);
+    assert_eq!(logs[0].1, "Reserved name");
+}
+
+macro_rules! or_bail {
+    ($option:expr) => {
+        match $option {
+            Some(value) => value,
+            None => bail_ok!(),
+        }
+    };
+}
+
+#[sysfail(LogSimply<&'static str>)]
+fn greet_first(query: Query<&Name>) {
+    let name = or_bail!(query.iter().next());
+    let () = Err("Nobody listens")?;
+    println!("Hello {name}");
+}
+
+#[test]
+fn bail_ok_in_macro() {
+    let mut world = World::new();
+    assert!(run_and_capture(&mut world, greet_first).is_empty());
+    world.spawn(Name::new("Joe"));
+    assert_eq!(run_and_capture(&mut world, greet_first).len(), 1);
+}
+
+#[derive(Resource)]
+struct Input(&'static str);
+
+#[sysfail(Log<&'static str>, forward)]
+fn parse_input(input: Res<Input>) -> Option<u32> {
+    if input.0.is_empty() {
+        return None;
+    }
+    let value = input.0.parse().map_err(|_| "Invalid number")?;
+    Some(value)
+}
+
+#[test]
+fn forward_option() {
+    let mut world = World::new();
+    world.insert_resource(Input("42"));
+    assert_eq!(world.run_system_once(parse_input), Some(42));
+    world.insert_resource(Input("Hello"));
+    assert_eq!(world.run_system_once(parse_input), None);
+    world.insert_resource(Input(""));
+    assert_eq!(world.run_system_once(parse_input), None);
+}
+
+#[sysfail(none_error)]
+fn first_name(query: Query<&Name>) {
+    let name = query.iter().next()?;
+    let _: u32 = name.as_str().parse()?;
+}
+
+#[test]
+fn none_error_option() {
+    let mut world = World::new();
+    let logs = run_and_capture(&mut world, first_name);
+    assert!(logs[0].1.starts_with("Unexpected `None` at "));
+    world.spawn(Name::new("Joe"));
+    let logs = run_and_capture(&mut world, first_name);
+    assert_eq!(logs[0].1, "invalid digit found in string");
+}
+
+#[sysfail(LogSimply<&'static str>)]
+fn check_all(query: Query<&Name>) {
+    for name in &query {
+        if name.as_str().is_empty() {
+            sysfail_handle!("Found an empty name");
+        }
+    }
+    let () = Err("Done checking")?;
+}
+
+#[test]
+fn handle_without_returning() {
+    let mut world = World::new();
+    world.spawn_batch([Name::new(""), Name::new("Joe"), Name::new("")]);
+    let logs = run_and_capture(&mut world, check_all);
+    assert_eq!(logs.len(), 3);
+}
+
+fn produce() -> u32 {
+    10
+}
+
+#[sysfail(LogSimply<&'static str>)]
+fn consume(In(value): In<u32>, mut commands: Commands) {
+    if value > 5 {
+        let () = Err("value too large")?;
+    }
+    commands.spawn(Name::new(format!("{value}")));
+}
+
+#[test]
+fn system_input() {
+    let mut app = App::new();
+    app.add_systems(Update, produce.pipe(consume));
+    app.update();
+
+    let consume_id = app.world.register_system(consume);
+    app.world.run_system_with_input(consume_id, 3).unwrap();
+    assert_eq!(app.world.query::<&Name>().iter(&app.world).count(), 1);
+}
+
+#[sysfail(LogSimply<&'static str>)]
+fn check_names(query: Query<(Entity, &Name)>) {
+    for (i, (entity, name)) in query.iter().enumerate() {
+        sysfail_context!("iteration" => i, "entity" => format!("{entity:?}"));
+        if name.is_empty() {
+            let () = Err("Empty name")?;
+        }
+    }
+}
+
+#[test]
+fn error_context() {
+    let mut world = World::new();
+    world.spawn_batch([Name::new("Joe"), Name::new("")]);
+    let logs = run_and_capture(&mut world, check_names);
+    assert_eq!(logs[0].1, "Empty name (iteration = 1, entity = 1v1)");
+}
+
+#[sysfail(Log<&'static str>)]
+fn handle_request() {
+    let conn_id = 42;
+    sysfail_tag!(conn_id);
+    let () = Err("Invalid request")?;
+}
+
+#[test]
+fn error_tag() {
+    let logs = run_and_capture(&mut World::new(), handle_request);
+    assert_eq!(logs[0].1, "Invalid request (conn_id = 42)");
+}
+
+#[sysfail(RoutedLog<NoNames>)]
+fn routed_request() {
+    let conn_id = 42;
+    sysfail_tag!(conn_id);
+    let () = Err("No names")?;
+}
+
+type RoutedNoNames = RoutedLog<NoNames>;
+
+#[sysfail(RoutedNoNames)]
+fn aliased_routed_request() {
+    let conn_id = 42;
+    sysfail_tag!(conn_id);
+    let () = Err("No names")?;
+}
+
+#[test]
+fn routed_error_tag() {
+    let expected = [(Level::INFO, "No names (conn_id = 42)".to_owned())];
+    let mut world = World::new();
+    assert_eq!(run_and_capture(&mut world, routed_request), expected);
+    // Without the callsites per level, still at `INFO` and with the context
+    assert_eq!(
+        run_and_capture(&mut world, aliased_routed_request),
+        expected
+    );
+}
+
+#[sysfail(Panic<&'static str>)]
+fn panicking_request() {
+    let conn_id = 42;
+    sysfail_tag!(conn_id);
+    let () = Err("Invalid request")?;
+}
+
+#[sysfail(Log<&'static str>)]
+fn without_context() {
+    let () = Err("No context")?;
+}
+
+#[test]
+fn context_cleared_on_panic() {
+    let mut world = World::new();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        world.run_system_once(panicking_request);
+    }));
+    assert!(result.is_err());
+    let logs = run_and_capture(&mut world, without_context);
+    assert_eq!(logs[0].1, "No context");
+}
+
+#[sysfail(LogSimply<&'static str>)]
+fn check_first_trailing(query: Query<&Name>) {
+    let name = query.iter().next().ok_or("No names")?;
+    check_name(name)
+}
+
+#[test]
+fn trailing_expression() {
+    let mut world = World::new();
+    world.spawn(Name::new(""));
+    let logs = run_and_capture(&mut world, check_first_trailing);
+    assert_eq!(logs[0].1, "Empty name");
+}
+
+#[sysfail(LogSimply<&'static str>)]
+fn spawn_named(mut commands: Commands) {
+    let name = Some("Joe").ok_or("No name")?;
+    commands.spawn(Name::new(name))
+}
+
+#[test]
+fn trailing_value_discarded() {
+    let mut world = World::new();
+    assert!(run_and_capture(&mut world, spawn_named).is_empty());
+    assert_eq!(world.query::<&Name>().iter(&world).count(), 1);
+}
+
+#[sysfail(Ignore)]
+fn spawn_named_ignored(mut commands: Commands) {
+    let name = Some("Joe").ok_or("No name")?;
+    commands.spawn(Name::new(name))
+}
+
+#[sysfail(Ignore)]
+fn check_first_ignored(query: Query<&Name>) {
+    let name = query.iter().next().ok_or("No names")?;
+    check_name(name)
+}
+
+#[test]
+fn trailing_expression_ignored() {
+    let mut world = World::new();
+    world.run_system_once(spawn_named_ignored);
+    assert_eq!(world.query::<&Name>().iter(&world).count(), 1);
+
+    world.spawn(Name::new(""));
+    assert!(run_and_capture(&mut world, check_first_ignored).is_empty());
+}
+
+fn add_context(error: impl fmt::Display) -> String {
+    format!("Failed to load the level: {error}")
+}
+
+#[sysfail(Log<String>, map = add_context)]
+fn load_level() {
+    let _: u32 = "one".parse()?;
+}
+
+#[test]
+fn map_option() {
+    let logs = run_and_capture(&mut World::new(), load_level);
+    assert_eq!(
+        logs[0].1,
+        "Failed to load the level: invalid digit found in string",
+    );
+}
+
+#[sysfail(Infallible)]
+fn infallible_system(query: Query<&Name>) {
+    for name in &query {
+        info!("{name}");
+    }
+}
+
+#[test]
+fn infallible_failure() {
+    let mut world = World::new();
+    world.spawn(Name::new("Joe"));
+    assert!(run_and_capture(&mut world, infallible_system).is_empty());
+}
+
+#[derive(Debug)]
+struct NoneFound<T>(PhantomData<T>);
+impl<T> fmt::Display for NoneFound<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "No {} found", type_name::<T>())
+    }
+}
+impl<T> Dedup for NoneFound<T> {
+    type ID = ();
+    fn identify(&self) {}
+}
+
+#[sysfail(Log<NoneFound<T>, Error>)]
+fn find_one<T: Component + fmt::Debug>(query: Query<&T>) {
+    if query.is_empty() {
+        let () = Err(NoneFound(PhantomData))?;
+    }
+}
+
+#[test]
+fn generic_error() {
+    let mut world = World::new();
+    let logs = run_and_capture(&mut world, find_one::<Transform>);
+    assert_eq!(
+        logs[0],
+        (
+            Level::ERROR,
+            format!("No {} found", type_name::<Transform>())
+        )
+    );
+}
+
+#[sysfail(F)]
+fn first_name_with<F: Failure + From<&'static str>>(query: Query<&Name>) {
+    let name = query.iter().next().ok_or("No names")?;
+    info!("{name}");
+}
+
+struct NoNames;
+impl fmt::Display for NoNames {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("No names")
+    }
+}
+impl From<&'static str> for NoNames {
+    fn from(_: &'static str) -> Self {
+        Self
+    }
+}
+impl LeveledError for NoNames {
+    fn level(&self) -> Level {
+        Level::INFO
+    }
+}
+
+#[test]
+fn generic_failure() {
+    let mut world = World::new();
+    assert!(run_and_capture(&mut world, first_name_with::<Ignore>).is_empty());
+
+    let logs = run_and_capture(
+        &mut world,
+        first_name_with::<LogSimply<&'static str, Error>>,
+    );
+    assert_eq!(logs, [(Level::ERROR, "No names".to_owned())]);
+
+    // The level of a generic `RoutedLog` is not known in the callsite
+    let logs = run_and_capture(&mut world, first_name_with::<RoutedLog<NoNames>>);
+    assert_eq!(logs, [(Level::INFO, "No names".to_owned())]);
+}
+
+#[sysfail(Log<&'static str>)]
+fn first_name_lifetimes<'w, 's>(query: Query<'w, 's, &'static Name>) {
+    let name = query.iter().next().ok_or("No names")?;
+    info!("{name}");
+}
+
+#[test]
+fn explicit_lifetimes() {
+    let logs = run_and_capture(&mut World::new(), first_name_lifetimes);
+    assert_eq!(logs[0].1, "No names");
+}
+
+struct WindowHandle(Rc<u32>);
+
+#[sysfail(Log<&'static str>)]
+fn use_window(window: NonSend<WindowHandle>) {
+    let () = Err(if *window.0 == 0 { "Invalid window" } else { "Valid window" })?;
+}
+
+#[test]
+fn non_send_system() {
+    let mut world = World::new();
+    world.insert_non_send_resource(WindowHandle(Rc::new(0)));
+    let logs = run_and_capture(&mut world, use_window);
+    assert_eq!(logs[0].1, "Invalid window");
+}
+
+/// Systems with other attributes.
+#[deny(missing_docs)]
+pub mod attributes {
+    use bevy::prelude::*;
+    use bevy_mod_sysfail::prelude::*;
+
+    /// Doc comments are kept, this is not a `missing_docs` error.
+    #[sysfail(Log<&'static str>)]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn documented(_time: Res<Time>) {
+        let () = Err("Documented")?;
+    }
+
+    /// Instrumented before `sysfail`.
+    #[tracing::instrument(skip_all)]
+    #[sysfail(Log<&'static str>)]
+    pub fn instrumented_before(time: Res<Time>) {
+        if time.elapsed_seconds() > 10.0 {
+            return;
+        }
+        let () = Err("Instrumented before")?;
+    }
+
+    /// Instrumented after `sysfail`.
+    #[sysfail(Log<&'static str>)]
+    #[tracing::instrument(skip_all)]
+    pub fn instrumented_after(_time: Res<Time>) {
+        let () = Err("Instrumented after")?;
+    }
+
+    // Neither `gone` nor `GoneSet` exist.
+    #[sysfail(Log<&'static str>, set = GoneSet)]
+    #[cfg(any())]
+    fn gone() {
+        let () = Err("Gone")?;
+    }
+    /// Not the set of `gone`.
+    pub struct GoneSet;
+}
+
+#[test]
+fn other_attributes() {
+    use attributes::{documented, instrumented_after, instrumented_before};
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_systems(
+        Update,
+        (documented, instrumented_before, instrumented_after),
+    );
+    app.update();
+}
+
+#[sysfail(Log<&'static str>)]
+fn weird_names(sysfail_params: Res<Time>, __sysfail_params: Query<&Transform>) {
+    let inner_system = sysfail_params.elapsed_seconds();
+    let param_items = __sysfail_params.iter().len();
+    let err: u32 = Err("failed")?;
+    println!("{inner_system} {param_items} {err:?}");
+}
+
+#[test]
+fn hygiene() {
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    let logs = run_and_capture(&mut world, weird_names);
+    assert_eq!(logs[0].1, "failed");
+}
@@ -0,0 +1,51 @@
+use std::error::Error;
+
+use bevy_mod_sysfail::{prelude::*, Callsite, Level};
+use tracing::callsite::DefaultCallsite;
+
+/// A `Failure` that fails to handle errors when it is `Some`.
+struct FailsWith(Option<&'static str>);
+
+impl Failure for FailsWith {
+    type Param = ();
+    const LEVEL: Level = Level::ERROR;
+
+    fn handle_error(self, param: (), callsite: Option<&'static impl Callsite>) {
+        let _ = self.handle_error_fallible(param, callsite);
+    }
+    fn handle_error_fallible(
+        self,
+        (): (),
+        _: Option<&'static impl Callsite>,
+    ) -> Result<(), Box<dyn Error>> {
+        self.0.map_or(Ok(()), |error| Err(error.into()))
+    }
+}
+
+fn handle(a: Option<&'static str>, b: Option<&'static str>) -> Result<(), String> {
+    let tee = Tee(FailsWith(a), FailsWith(b));
+    let result = tee.handle_error_fallible(((), ()), None::<&'static DefaultCallsite>);
+    result.map_err(|error| error.to_string())
+}
+
+#[test]
+fn both_fail() {
+    let error = "Failed to write, and Failed to send";
+    assert_eq!(
+        handle(Some("Failed to write"), Some("Failed to send")),
+        Err(error.to_owned())
+    );
+}
+
+#[test]
+fn one_fails() {
+    assert_eq!(
+        handle(Some("Failed to write"), None),
+        Err("Failed to write".to_owned())
+    );
+    assert_eq!(
+        handle(None, Some("Failed to send")),
+        Err("Failed to send".to_owned())
+    );
+    assert_eq!(handle(None, None), Ok(()));
+}
@@ -0,0 +1,8 @@
+use bevy_mod_sysfail::prelude::*;
+
+#[sysfail(Log<&'static str>)]
+async fn failable_system() {
+    let () = Err("Async systems are rejected")?;
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use bevy::prelude::*;
+use bevy_mod_sysfail::prelude::*;
+
+#[exclusive_sysfail(Log<&'static str>)]
+fn exclusive_system(_world: &mut World) {
+    let () = Err("Log needs a SystemParam, use LogSimply instead")?;
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use bevy_mod_sysfail::prelude::*;
+
+#[exclusive_sysfail(LogSimply<&'static str>)]
+fn exclusive_system(_world: &bevy::prelude::World) {
+    let () = Err("Exclusive systems need a &mut World")?;
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use bevy_mod_sysfail::prelude::*;
+
+#[sysfail(Log<&'static str>, forward)]
+fn failable_system() {
+    let () = Err("forward needs a return type")?;
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use std::convert::Infallible;
+
+use bevy_mod_sysfail::prelude::*;
+
+#[sysfail(Infallible)]
+fn infallible_system() {
+    let () = Err("Infallible systems can't fail")?;
+}
+
+fn main() {}
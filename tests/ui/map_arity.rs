@@ -0,0 +1,12 @@
+use bevy_mod_sysfail::prelude::*;
+
+fn add_context(error: &str, context: &str) -> String {
+    format!("{context}: {error}")
+}
+
+#[sysfail(Log<String>, map = add_context)]
+fn failable_system() {
+    let () = Err("map takes a single argument")?;
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use bevy_mod_sysfail::prelude::*;
+
+static MY_MODE: () = ();
+
+#[sysfail(Log<&'static str>, mode_ref = MY_MODE)]
+fn with_mode_ref() {
+    let () = Err("mode_ref needs Configured")?;
+}
+
+#[sysfail(Configured<&'static str>)]
+fn without_mode_ref() {
+    let () = Err("Configured needs mode_ref")?;
+}
+
+fn main() {}
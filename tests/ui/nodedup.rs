@@ -0,0 +1,8 @@
+use bevy_mod_sysfail::prelude::*;
+
+#[sysfail(Emit<&'static str>, nodedup)]
+fn failable_system() {
+    let () = Err("nodedup only applies to Log")?;
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use bevy_mod_sysfail::prelude::*;
+
+#[sysfail(Log<&'static str>, field = "sysfail")]
+fn failable_system() {
+    let () = Err("The sysfail field is reserved")?;
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use bevy_mod_sysfail::prelude::*;
+
+#[sysfail(Log<&'static str>)]
+fn failable_system() -> Result<(), &'static str> {
+    Err("Return types are rejected")
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use bevy_mod_sysfail::prelude::*;
+
+#[sysfail(Log<&'static str>, target = game::net)]
+fn failable_system() {
+    let () = Err("The target must be a string literal")?;
+}
+
+fn main() {}
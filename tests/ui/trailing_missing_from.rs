@@ -0,0 +1,22 @@
+use bevy_mod_sysfail::prelude::*;
+
+#[derive(Debug)]
+struct MyError;
+impl std::fmt::Display for MyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("MyError")
+    }
+}
+#[derive(Debug)]
+struct OtherError;
+
+fn check() -> Result<(), OtherError> {
+    Err(OtherError)
+}
+
+#[sysfail(LogSimply<MyError>)]
+fn failable_system() {
+    check()
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use bevy_mod_sysfail::prelude::*;
+
+#[sysfail(Log<&'static str>, nodedupe)]
+fn failable_system() {
+    let () = Err("Unknown options are rejected")?;
+}
+
+fn main() {}